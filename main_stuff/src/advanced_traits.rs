@@ -0,0 +1,1337 @@
+use std::borrow::{Borrow, Cow};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Deref, DerefMut, Index, IndexMut, Mul, Neg, Sub};
+use std::slice;
+use std::str::FromStr;
+use std::vec;
+
+use crate::report::SectionReport;
+
+//A key-value cache with the key and value as associated types, see cache::Cache.
+pub mod cache;
+//A graph with its node ids and edge weights as associated types, see graph::Graph.
+pub mod graph;
+//New methods on every Iterator through an extension trait, see iterator_ext::IteratorExt.
+pub mod iterator_ext;
+//Generic associated types, see lending::LendingIterator.
+pub mod lending;
+//Zipping two collections with the output types as associated types, see pair::Zip.
+pub mod pair;
+//Storage behind a trait with associated types, and a decorator over it, see
+// repository::Repository and repository::LoggedRepo.
+pub mod repository;
+//A trait that only this crate can implement, see sealed::Summary and sealed::SummaryExt.
+pub mod sealed;
+//Trait objects and dynamic dispatch, see shapes::Shape.
+pub mod shapes;
+//Associated constants and why they keep a trait from being a trait object, see
+// sided_shapes::SidedShape and sided_shapes::SidedShapeDyn.
+pub mod sided_shapes;
+//A newtype that hides most of the Vec it wraps, see stack::Stack.
+pub mod stack;
+//Storing values of any type and checking the type at runtime, see type_erased::TypeErased.
+pub mod type_erased;
+//Operator overloading with Add<Rhs> between units of length, see units::Millimeters.
+pub mod units;
+//A trait object built by hand out of function pointers, see vtable::ShapeBox.
+pub mod vtable;
+
+//There are things called associated types. These are similar to generics except that with
+// associated types the type can only be implemented once. This allows for the type to not
+// need to be explicitly specified each time.
+
+pub trait FooAssociated {
+    type Item;
+
+    fn foo_associated(&mut self) -> Option<Self::Item>;
+}
+
+pub trait FooGeneric<T> {
+    fn foo_generic(&mut self) -> Option<T>;
+}
+
+pub struct BarStruct;
+
+impl FooAssociated for BarStruct {
+    type Item = u32;
+
+    fn foo_associated(&mut self) -> Option<Self::Item> {
+        Some(3)
+    }
+}
+
+//Iterator is the real example of an associated type. Each iterator yields exactly one kind of
+// value, so Item is an associated type instead of a generic. Otherwise every call to next()
+// would need to say which Iterator<T> it meant, just like FooGeneric above.
+pub struct Counter {
+    count: u32,
+    limit: u32,
+}
+
+impl Counter {
+    //Counts from 1 up to and including limit.
+    pub fn new(limit: u32) -> Counter {
+        Counter { count: 0, limit }
+    }
+}
+
+impl Iterator for Counter {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.count < self.limit {
+            self.count += 1;
+            Some(self.count)
+        } else {
+            None
+        }
+    }
+}
+
+//0, 1, 1, 2, 3, 5, ... up to the biggest Fibonacci number that fits in a u64. The number after
+// each one is worked out ahead of time with checked_add(), so the sequence just ends instead of
+// overflowing.
+pub struct Fibonacci {
+    current: Option<u64>,
+    next: Option<u64>,
+}
+
+impl Fibonacci {
+    pub fn new() -> Fibonacci {
+        Fibonacci {
+            current: Some(0),
+            next: Some(1),
+        }
+    }
+}
+
+impl Default for Fibonacci {
+    fn default() -> Fibonacci {
+        Fibonacci::new()
+    }
+}
+
+impl Iterator for Fibonacci {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let value = self.current?;
+        self.current = self.next;
+        self.next = self.next.and_then(|next| next.checked_add(value));
+        Some(value)
+    }
+}
+
+//Because both are iterators, all of the adapters from the standard library work on them for free.
+// This pairs up 1..=5 with the first 5 Fibonacci numbers, multiplies each pair and adds up the
+// even products: 1*0 + 2*1 + 4*2 = 10.
+pub fn counter_fibonacci_sum() -> u64 {
+    Counter::new(5)
+        .zip(Fibonacci::new())
+        .map(|(count, fib)| count as u64 * fib)
+        .filter(|product| product % 2 == 0)
+        .sum()
+}
+
+//This code will not compile because only a single implementation can exist for an associated
+// type.
+// impl FooAssociated for BarStruct {
+//     type Item = String;
+//
+//     fn foo_associated(&mut self) -> Option<Self::Item> {
+//         Some(String::from("associated"))
+//     }
+// }
+
+impl FooGeneric<u32> for BarStruct {
+    fn foo_generic(&mut self) -> Option<u32> {
+        Some(5)
+    }
+}
+
+impl FooGeneric<String> for BarStruct {
+    fn foo_generic(&mut self) -> Option<String> {
+        Some(String::from("generic"))
+    }
+}
+
+//The same choice between implementations, wrapped up in a function. T cannot be worked out from
+// the argument since BarStruct implements FooGeneric for more than one T, so the caller picks
+// it with the turbofish, `pick::<String, _>(&mut bar)`. The `_` leaves B up to the compiler.
+pub fn pick<T, B: FooGeneric<T>>(b: &mut B) -> Option<T> {
+    b.foo_generic()
+}
+
+//A default type can be set for a parameter. The two kinds of types work together here, T is what
+// goes in and is picked by the caller, while Output is what comes out and is decided by each
+// implementation.
+pub trait Winner<T = u32> {
+    type Output;
+
+    fn win(self, num: T) -> Self::Output;
+}
+
+//Only the associated type is needed to name the result, `W::Output`, without knowing which type
+// W actually is.
+pub fn winner_of<W: Winner<T>, T>(w: W, num: T) -> W::Output {
+    w.win(num)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Score(pub u32);
+
+impl Display for Score {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+pub struct Check;
+
+//Notice that a type does not need to be explicitly specified here. Instead, the default type
+// is used.
+impl Winner for Check {
+    type Output = Score;
+
+    fn win(self, num: u32) -> Score {
+        Score(num)
+    }
+}
+
+//The same default u32 going in, but a completely different Output coming out.
+pub struct Lottery {
+    pub winning_number: u32,
+}
+
+impl Winner for Lottery {
+    type Output = bool;
+
+    fn win(self, num: u32) -> bool {
+        num == self.winning_number
+    }
+}
+
+//The standard library uses default type parameters for operator overloading. Add is declared as
+// `trait Add<Rhs = Self>`, so `impl Add for Point` is adding a Point to a Point. Mul below gives
+// an explicit right hand side instead, so a Point can be multiplied by an i32.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point {
+            x: self.x + other.x,
+            y: self.y + other.y,
+        }
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point {
+            x: self.x - other.x,
+            y: self.y - other.y,
+        }
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point { x: -self.x, y: -self.y }
+    }
+}
+
+impl Mul<i32> for Point {
+    type Output = Point;
+
+    fn mul(self, scalar: i32) -> Point {
+        Point {
+            x: self.x * scalar,
+            y: self.y * scalar,
+        }
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+//point[0] is x and point[1] is y, like a two element array.
+impl Index<usize> for Point {
+    type Output = i32;
+
+    fn index(&self, index: usize) -> &i32 {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            _ => panic!("a Point only has indexes 0 (x) and 1 (y), but the index was {}", index),
+        }
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+//Traits can also have associated constants. Each implementation has to give a value, unless the
+// trait provides a default, and default methods can use them through `Self::`.
+pub trait Animal {
+    const LEGS: u32;
+
+    fn baby_name() -> &'static str;
+
+    fn legs(&self) -> u32 {
+        Self::LEGS
+    }
+}
+
+pub struct Dog;
+
+pub struct Spider;
+
+//Dog also has its own baby_name(). Since neither one takes self, `Dog::baby_name()` always calls
+// this one and `Animal::baby_name()` does not compile, because there is nothing to tell Rust which
+// implementation of Animal is meant. Only `<Dog as Animal>::baby_name()` reaches the trait's.
+impl Dog {
+    pub fn baby_name() -> &'static str {
+        "Spot"
+    }
+}
+
+impl Animal for Dog {
+    const LEGS: u32 = 4;
+
+    fn baby_name() -> &'static str {
+        "puppy"
+    }
+}
+
+impl Animal for Spider {
+    const LEGS: u32 = 8;
+
+    fn baby_name() -> &'static str {
+        "spiderling"
+    }
+}
+
+//Fully qualified syntax can be used when there are conflicting names.
+
+pub trait Arm {
+    fn pain(&self) -> &'static str {
+        "My arm feels good"
+    }
+}
+
+pub trait Leg {
+    fn pain(&self) -> &'static str {
+        "My leg is a little sore"
+    }
+}
+
+pub struct Human;
+
+impl Arm for Human {}
+
+impl Leg for Human {}
+
+impl Human {
+    pub fn pain(&self) -> &'static str {
+        "Overall I feel good"
+    }
+}
+
+//The example from the book. Unlike pain() above, neither trait has a default so each one is
+// implemented for Human on its own.
+pub trait Pilot {
+    fn fly(&self) -> &'static str;
+}
+
+pub trait Wizard {
+    fn fly(&self) -> &'static str;
+}
+
+impl Pilot for Human {
+    fn fly(&self) -> &'static str {
+        "This is your captain speaking."
+    }
+}
+
+impl Wizard for Human {
+    fn fly(&self) -> &'static str {
+        "Up!"
+    }
+}
+
+impl Human {
+    pub fn fly(&self) -> &'static str {
+        "*waving arms furiously*"
+    }
+}
+
+//Supertraits are traits that are required to implement another trait.
+pub trait ShowStuff: Display + AsDisplay {
+    fn show_stuff(&self) -> String {
+        format!("running show_stuff() {}", self)
+    }
+}
+
+//Turning a &dyn ShowStuff into a &dyn Display. Newer versions of Rust can do this with a plain
+// `as`, but as_display() spells out how it works. The method has to be written once per type
+// because the coercion from &T to &dyn Display needs to know T, which a default method in
+// ShowStuff would not (it would need Self: Sized, and then it could not be called on a dyn). The
+// blanket impl writes it for every Display type, and since AsDisplay is a supertrait of
+// ShowStuff, as_display() ends up in the ShowStuff vtable. None of the ShowStuff implementations
+// had to change.
+pub trait AsDisplay {
+    fn as_display(&self) -> &dyn Display;
+}
+
+impl<T: Display> AsDisplay for T {
+    fn as_display(&self) -> &dyn Display {
+        self
+    }
+}
+
+//`(**item)` goes through the Box to the dyn ShowStuff. Calling as_display() on the Box itself
+// would also work, but only because Box<dyn ShowStuff> is Display too, and then it is the Box
+// being formatted rather than the upcast value.
+pub fn print_all_displays(items: &[Box<dyn ShowStuff>]) -> Vec<String> {
+    items.iter().map(|item| (**item).as_display().to_string()).collect()
+}
+
+//The supertrait example from the book. OutlinePrint can use to_string() in its default method
+// because every type that implements it must also implement Display. The box is sized in chars
+// rather than bytes so that something like "é" (two bytes) still takes up one column, and output
+// with several lines is sized to the longest of them.
+pub trait OutlinePrint: Display {
+    fn outline_string(&self) -> String {
+        let output = self.to_string();
+        let lines: Vec<&str> = output.lines().collect();
+        let width = lines.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+
+        let border = "*".repeat(width + 4);
+        let blank = format!("*{}*", " ".repeat(width + 2));
+        let mut framed = vec![border.clone(), blank.clone()];
+        for line in lines {
+            let padding = " ".repeat(width - line.chars().count());
+            framed.push(format!("* {}{} *", line, padding));
+        }
+        framed.push(blank);
+        framed.push(border);
+        framed.join("\n")
+    }
+
+    fn outline_print(&self) {
+        println!("{}", self.outline_string());
+    }
+}
+
+impl ShowStuff for Point {}
+
+impl OutlinePrint for Point {}
+
+//This was called `Box` when it was nested inside of a function. At module scope that name would
+// shadow the standard library Box for everything else in the module, so it was renamed.
+pub struct LenBox {
+    pub len: i32,
+}
+
+//Display must be implemented in order to implement the trait ShowStuff.
+impl Display for LenBox {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.len)
+    }
+}
+
+impl ShowStuff for LenBox {}
+
+impl OutlinePrint for LenBox {}
+
+//A Vec can only hold one type, but every Box<dyn ShowStuff> is the same type no matter what is
+// inside of it. Which show_stuff() runs is looked up through the vtable at runtime, and since
+// Display is a supertrait, the default show_stuff() can format whatever the item turns out to be.
+#[derive(Default)]
+pub struct Gallery {
+    items: Vec<Box<dyn ShowStuff>>,
+}
+
+impl Gallery {
+    pub fn new() -> Gallery {
+        Gallery::default()
+    }
+
+    pub fn add(&mut self, item: impl ShowStuff + 'static) {
+        self.items.push(Box::new(item));
+    }
+
+    //The show_stuff() output of every item, in the order they were added.
+    pub fn show_all(&self) -> Vec<String> {
+        self.items.iter().map(|item| item.show_stuff()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+//There is also something called the `newtype pattern`. The terminology is apparently taken
+// from Haskell. Essentially there is a rule that restricts from implementing an external trait
+// on an external type. In order to get around this, a wrapper can be made for the external
+// type and the trait can be implemented on the wrapped. The example given in the book for this
+// is listed below. Apparently the compiler will use elision to remove any performance penalty
+// when this pattern is used.
+//Deriving works on a wrapper as long as the wrapped type implements the trait, so it can still be
+// compared, hashed and used as a map key just like the Vec inside of it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Wrapper(pub Vec<String>);
+
+impl Display for Wrapper {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "[{}]", self.0.join(", "))
+    }
+}
+
+impl ShowStuff for Wrapper {}
+
+//As a slice, so functions that take AsRef<[String]> accept a Wrapper. Borrow<[String]> lets a
+// HashMap or HashSet of Wrappers be searched with a plain &[String]. That is only correct because
+// the derived Hash and Eq of a Wrapper are those of its Vec, which are in turn those of the slice.
+impl AsRef<[String]> for Wrapper {
+    fn as_ref(&self) -> &[String] {
+        &self.0
+    }
+}
+
+impl Borrow<[String]> for Wrapper {
+    fn borrow(&self) -> &[String] {
+        &self.0
+    }
+}
+
+//The downside of a newtype is that it does not have any of the methods of the type it wraps.
+// Implementing Deref fixes most of that, `w.len()` and `w.push(..)` both reach the Vec through
+// auto-deref. Traits are not found through Deref though, so the ones that make Wrapper work with
+// collect(), for loops and extend() are each implemented below by handing off to the Vec.
+impl Deref for Wrapper {
+    type Target = Vec<String>;
+
+    fn deref(&self) -> &Vec<String> {
+        &self.0
+    }
+}
+
+impl DerefMut for Wrapper {
+    fn deref_mut(&mut self) -> &mut Vec<String> {
+        &mut self.0
+    }
+}
+
+impl FromIterator<String> for Wrapper {
+    fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> Wrapper {
+        Wrapper(iter.into_iter().collect())
+    }
+}
+
+impl Extend<String> for Wrapper {
+    fn extend<I: IntoIterator<Item = String>>(&mut self, iter: I) {
+        self.0.extend(iter);
+    }
+}
+
+impl IntoIterator for Wrapper {
+    type Item = String;
+    type IntoIter = vec::IntoIter<String>;
+
+    fn into_iter(self) -> vec::IntoIter<String> {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Wrapper {
+    type Item = &'a String;
+    type IntoIter = slice::Iter<'a, String>;
+
+    fn into_iter(self) -> slice::Iter<'a, String> {
+        self.0.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut Wrapper {
+    type Item = &'a mut String;
+    type IntoIter = slice::IterMut<'a, String>;
+
+    fn into_iter(self) -> slice::IterMut<'a, String> {
+        self.0.iter_mut()
+    }
+}
+
+//Indexing hands off to the Vec, so an index past the end panics with the same message it would.
+impl Index<usize> for Wrapper {
+    type Output = String;
+
+    fn index(&self, index: usize) -> &String {
+        &self.0[index]
+    }
+}
+
+impl IndexMut<usize> for Wrapper {
+    fn index_mut(&mut self, index: usize) -> &mut String {
+        &mut self.0[index]
+    }
+}
+
+crate::impl_from_for_newtype!(Wrapper(Vec<String>));
+
+//The same newtype for any element type. Display is only implemented when T: Display, so a
+// VecWrapper<i32> prints as "[1, 2]" while a VecWrapper of something without Display still works,
+// it just cannot be printed. The elements are written one at a time instead of joined, since
+// join() only works on strings.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct VecWrapper<T>(pub Vec<T>);
+
+impl<T> VecWrapper<T> {
+    pub fn new() -> VecWrapper<T> {
+        VecWrapper(Vec::new())
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.0.push(value);
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<T: Display> Display for VecWrapper<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "[")?;
+        for (i, value) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", value)?;
+        }
+        write!(f, "]")
+    }
+}
+
+crate::impl_from_for_newtype!(<T> VecWrapper(Vec<T>));
+
+//Wrapper always owns its strings, so wrapping a few string literals means allocating a String for
+// each of them. Cow (clone on write) holds either a borrowed &str or an owned String, so borrowed
+// strings stay borrowed and only the ones that were already owned cost an allocation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct CowWrapper<'a>(pub Vec<Cow<'a, str>>);
+
+impl<'a> CowWrapper<'a> {
+    pub fn new() -> CowWrapper<'a> {
+        CowWrapper(Vec::new())
+    }
+
+    //Takes either a &str or a String, so the two can be mixed in the same wrapper.
+    pub fn push(&mut self, string: impl Into<Cow<'a, str>>) {
+        self.0.push(string.into());
+    }
+}
+
+//Cow<str> can be borrowed as a str, so join() works on it the same way it does for String.
+impl Display for CowWrapper<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "[{}]", self.0.join(", "))
+    }
+}
+
+impl<'a> From<&[&'a str]> for CowWrapper<'a> {
+    fn from(strings: &[&'a str]) -> CowWrapper<'a> {
+        CowWrapper(strings.iter().map(|&string| Cow::Borrowed(string)).collect())
+    }
+}
+
+impl From<Vec<String>> for CowWrapper<'_> {
+    fn from(strings: Vec<String>) -> Self {
+        CowWrapper(strings.into_iter().map(Cow::Owned).collect())
+    }
+}
+
+//A newtype can also promise something about what it wraps. The Vec inside of NonEmptyWrapper is
+// private, so the only ways to make one are try_from() and parse(), and both check that there is
+// at least one string and that none of them are empty. Deref only goes to a slice, not the Vec,
+// so nothing can be removed through it afterwards.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NonEmptyWrapper(Vec<String>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NonEmptyError {
+    NoStrings,
+    EmptyString { index: usize },
+}
+
+impl Display for NonEmptyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            NonEmptyError::NoStrings => write!(f, "there must be at least one string"),
+            NonEmptyError::EmptyString { index } => {
+                write!(f, "the string at index {} is empty", index)
+            }
+        }
+    }
+}
+
+impl std::error::Error for NonEmptyError {}
+
+impl TryFrom<Vec<String>> for NonEmptyWrapper {
+    type Error = NonEmptyError;
+
+    fn try_from(strings: Vec<String>) -> Result<NonEmptyWrapper, NonEmptyError> {
+        if strings.is_empty() {
+            return Err(NonEmptyError::NoStrings);
+        }
+        if let Some(index) = strings.iter().position(|string| string.is_empty()) {
+            return Err(NonEmptyError::EmptyString { index });
+        }
+        Ok(NonEmptyWrapper(strings))
+    }
+}
+
+impl From<NonEmptyWrapper> for Vec<String> {
+    fn from(wrapper: NonEmptyWrapper) -> Vec<String> {
+        wrapper.0
+    }
+}
+
+//Parses "a, b, c". Whitespace around each string is trimmed before it is checked, so "a, , c"
+// fails at index 1 the same way vec!["a", "", "c"] would.
+impl FromStr for NonEmptyWrapper {
+    type Err = NonEmptyError;
+
+    fn from_str(s: &str) -> Result<NonEmptyWrapper, NonEmptyError> {
+        if s.trim().is_empty() {
+            return Err(NonEmptyError::NoStrings);
+        }
+        let strings: Vec<String> = s.split(',').map(|part| part.trim().to_string()).collect();
+        NonEmptyWrapper::try_from(strings)
+    }
+}
+
+impl Deref for NonEmptyWrapper {
+    type Target = [String];
+
+    fn deref(&self) -> &[String] {
+        &self.0
+    }
+}
+
+//Written the same way from_str() reads it, so to_string() and parse() round-trip.
+impl Display for NonEmptyWrapper {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.0.join(", "))
+    }
+}
+
+//A generic newtype. The orphan rule stops Display from being implemented for some T that lives in
+// another crate, but Pretty<T> is local, so it can be given a Display for any T. The impl is only
+// there when T: Debug though, so Pretty<Vec<i32>> can be printed and a Pretty of something without
+// Debug simply has no Display. Deriving does the same thing, the derived PartialEq, Eq and Hash
+// each only exist when T has them too.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Pretty<T>(T);
+
+impl<T> Pretty<T> {
+    pub fn new(value: T) -> Pretty<T> {
+        Pretty(value)
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+
+    pub fn as_inner(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: fmt::Debug> Display for Pretty<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{:#?}", self.0)
+    }
+}
+
+pub fn prettify<T: fmt::Debug>(value: T) -> Pretty<T> {
+    Pretty(value)
+}
+
+pub fn run() -> SectionReport {
+    let mut report = SectionReport::new("traits");
+
+    let mut bar = BarStruct {};
+
+    //Note that the generics are more complex to call. However, if there is only a single
+    // implementation this is not true in this case. It seems to be that there are some benefits
+    // to be had with the compiler and that it can make more guarantees here. The way of calling
+    // the different generics is know as `fully qualified syntax`. It is explored a little bit more
+    // below
+    report.push(format!(
+        "associated {:?} generic::u32 {:?} generic::String {:?}",
+        bar.foo_associated(),
+        <BarStruct as FooGeneric<u32>>::foo_generic(&mut bar),
+        <BarStruct as FooGeneric<String>>::foo_generic(&mut bar),
+    ));
+
+    report.push(format!("counter zipped with fibonacci: {}", counter_fibonacci_sum()));
+
+    let check = Check {};
+
+    report.push(format!("win {}", check.win(4)));
+
+    //The default right hand side next to an explicit one.
+    let a = Point { x: 1, y: 0 };
+    let b = Point { x: 2, y: 3 };
+    report.push(format!("{} + {} = {}", a, b, a + b));
+    report.push(format!("{} * 3 = {}", b, b * 3));
+    report.push(format!("a dog has {} legs and a spider has {}", Dog.legs(), Spider.legs()));
+    report.push(format!(
+        "500 mm + 2 m = {}",
+        units::Millimeters(500) + units::Meters(2)
+    ));
+
+    let human = Human {};
+
+    //The below is fully qualified syntax. By default the Human implementation of pain() is called.
+    // However, if other implementations of pain() are needed, they can also be called using the
+    // below syntax.
+    report.push(human.pain());
+    report.push(Arm::pain(&human));
+    report.push(Leg::pain(&human));
+
+    //Note that fully qualified syntax can be used anywhere. However, Rust can figure out most of
+    // it and so there is no need.
+    report.push(<Human as Leg>::pain(&human));
+    report.push(human.fly());
+    report.push(Pilot::fly(&human));
+    report.push(Wizard::fly(&human));
+
+    //Associated functions have no self to work out the type from, so this is where fully
+    // qualified syntax is actually needed.
+    report.push(format!(
+        "A baby dog is called a {}, not {}",
+        <Dog as Animal>::baby_name(),
+        Dog::baby_name()
+    ));
+
+    let my_box = LenBox { len: 12 };
+
+    report.push(my_box.show_stuff());
+
+    let w = Wrapper(vec![String::from("hello"), String::from("world")]);
+    report.push(format!("w = {}", w));
+
+    let mut gallery = Gallery::new();
+    gallery.add(my_box);
+    gallery.add(Point { x: 1, y: 2 });
+    gallery.add(w);
+    for shown in gallery.show_all() {
+        report.push(shown);
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn associated_and_generic_implementations() {
+        let mut bar = BarStruct;
+
+        assert_eq!(bar.foo_associated(), Some(3));
+        assert_eq!(<BarStruct as FooGeneric<u32>>::foo_generic(&mut bar), Some(5));
+        assert_eq!(
+            <BarStruct as FooGeneric<String>>::foo_generic(&mut bar),
+            Some(String::from("generic"))
+        );
+    }
+
+    #[test]
+    fn pick_selects_the_implementation() {
+        let mut bar = BarStruct;
+
+        assert_eq!(pick::<u32, _>(&mut bar), Some(5));
+        assert_eq!(pick::<String, _>(&mut bar), Some(String::from("generic")));
+
+        let picked: Option<u32> = pick(&mut bar);
+        assert_eq!(picked, Some(5));
+    }
+
+    #[test]
+    fn counter_counts_to_its_limit() {
+        assert_eq!(Counter::new(5).collect::<Vec<_>>(), [1, 2, 3, 4, 5]);
+        assert_eq!(Counter::new(0).next(), None);
+    }
+
+    #[test]
+    fn fibonacci_sequence() {
+        let start: Vec<u64> = Fibonacci::new().take(10).collect();
+
+        assert_eq!(start, [0, 1, 1, 2, 3, 5, 8, 13, 21, 34]);
+    }
+
+    #[test]
+    fn fibonacci_stops_before_overflow() {
+        let all: Vec<u64> = Fibonacci::new().collect();
+
+        //F(0) through F(93), F(94) does not fit in a u64.
+        assert_eq!(all.len(), 94);
+        assert_eq!(*all.last().unwrap(), 12_200_160_415_121_876_738);
+        assert!(all[92].checked_add(all[93]).is_none());
+    }
+
+    #[test]
+    fn zip_map_filter_sum() {
+        assert_eq!(counter_fibonacci_sum(), 10);
+    }
+
+    #[test]
+    fn winner_uses_default_type_parameter() {
+        assert_eq!(Check.win(4), Score(4));
+    }
+
+    #[test]
+    fn winner_output_depends_on_the_implementation() {
+        let score: Score = winner_of(Check, 7);
+        let won: bool = winner_of(Lottery { winning_number: 7 }, 7);
+
+        assert_eq!(score, Score(7));
+        assert!(won);
+        assert!(!winner_of(Lottery { winning_number: 7 }, 8));
+    }
+
+    #[test]
+    fn associated_constants() {
+        assert_eq!(Dog::LEGS, 4);
+        assert_eq!(Spider::LEGS, 8);
+        assert_eq!(Dog.legs(), 4);
+        assert_eq!(Spider.legs(), 8);
+    }
+
+    #[test]
+    fn baby_name_needs_fully_qualified_syntax() {
+        assert_eq!(Dog::baby_name(), "Spot");
+        assert_eq!(<Dog as Animal>::baby_name(), "puppy");
+        assert_eq!(<Spider as Animal>::baby_name(), "spiderling");
+        assert_eq!(Spider::baby_name(), "spiderling");
+    }
+
+    #[test]
+    fn point_operators() {
+        let a = Point { x: 1, y: 2 };
+        let b = Point { x: 3, y: -4 };
+
+        assert_eq!(a + b, Point { x: 4, y: -2 });
+        assert_eq!(a - b, Point { x: -2, y: 6 });
+        assert_eq!(-a, Point { x: -1, y: -2 });
+        assert_eq!(b * 2, Point { x: 6, y: -8 });
+
+        let mut c = a;
+        c += b;
+        c += b;
+        assert_eq!(c, Point { x: 7, y: -6 });
+    }
+
+    #[test]
+    fn point_chained_arithmetic() {
+        let a = Point { x: 1, y: 1 };
+        let b = Point { x: 2, y: 3 };
+        let c = Point { x: 5, y: 5 };
+
+        assert_eq!((a + b) * 2 - c, Point { x: 1, y: 3 });
+        assert_eq!(-(a - c) * 3, Point { x: 12, y: 12 });
+    }
+
+    #[test]
+    fn point_index_and_display() {
+        let p = Point { x: 7, y: -1 };
+
+        assert_eq!((p[0], p[1]), (7, -1));
+        assert_eq!(p.to_string(), "(7, -1)");
+        assert_eq!(Point::default().to_string(), "(0, 0)");
+    }
+
+    #[test]
+    #[should_panic(expected = "a Point only has indexes 0 (x) and 1 (y), but the index was 2")]
+    fn point_index_out_of_range_panics() {
+        let _ = Point::default()[2];
+    }
+
+    #[test]
+    fn outline_short_value() {
+        let expected = "\
+**********
+*        *
+* (1, 3) *
+*        *
+**********";
+
+        assert_eq!(Point { x: 1, y: 3 }.outline_string(), expected);
+        assert_eq!(LenBox { len: 5 }.outline_string(), "*****\n*   *\n* 5 *\n*   *\n*****");
+    }
+
+    struct Text(&'static str);
+
+    impl Display for Text {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            write!(f, "{}", self.0)
+        }
+    }
+
+    impl OutlinePrint for Text {}
+
+    #[test]
+    fn outline_counts_chars_not_bytes() {
+        let expected = "\
+***************
+*             *
+* héllo wörld *
+*             *
+***************";
+
+        assert_eq!(Text("héllo wörld").outline_string(), expected);
+    }
+
+    #[test]
+    fn outline_multiple_lines() {
+        let expected = "\
+*********
+*       *
+* one   *
+* three *
+* two   *
+*       *
+*********";
+
+        assert_eq!(Text("one\nthree\ntwo").outline_string(), expected);
+    }
+
+    #[test]
+    fn len_box_displays_len() {
+        assert_eq!(LenBox { len: 12 }.to_string(), "12");
+    }
+
+    #[test]
+    fn fully_qualified_syntax_picks_the_implementation() {
+        let human = Human;
+
+        assert_eq!(human.pain(), "Overall I feel good");
+        assert_eq!(Arm::pain(&human), "My arm feels good");
+        assert_eq!(<Human as Leg>::pain(&human), "My leg is a little sore");
+    }
+
+    #[test]
+    fn fly_picks_the_implementation() {
+        let human = Human;
+
+        assert_eq!(human.fly(), "*waving arms furiously*");
+        assert_eq!(Human::fly(&human), "*waving arms furiously*");
+        assert_eq!(Pilot::fly(&human), "This is your captain speaking.");
+        assert_eq!(Wizard::fly(&human), "Up!");
+        assert_eq!(<Human as Pilot>::fly(&human), "This is your captain speaking.");
+        assert_eq!(<Human as Wizard>::fly(&human), "Up!");
+    }
+
+    #[test]
+    fn report_contains_key_lines() {
+        let report = run();
+
+        assert_eq!(report.name, "traits");
+        assert_eq!(
+            report.count(r#"associated Some(3) generic::u32 Some(5) generic::String Some("generic")"#),
+            1
+        );
+        assert_eq!(report.count("win 4"), 1);
+        assert_eq!(report.count("counter zipped with fibonacci: 10"), 1);
+        assert_eq!(report.count("(1, 0) + (2, 3) = (3, 3)"), 1);
+        assert_eq!(report.count("(2, 3) * 3 = (6, 9)"), 1);
+        assert_eq!(report.count("500 mm + 2 m = 2500 mm"), 1);
+        assert_eq!(report.count("My leg is a little sore"), 2);
+        assert_eq!(report.count("Up!"), 1);
+        assert_eq!(report.count("A baby dog is called a puppy, not Spot"), 1);
+        assert_eq!(report.count("running show_stuff() 12"), 2);
+        assert_eq!(report.count("running show_stuff() [hello, world]"), 1);
+        assert_eq!(report.count("w = [hello, world]"), 1);
+    }
+
+    #[test]
+    fn wrapper_displays_bracketed_list() {
+        let w = Wrapper(vec![String::from("hello"), String::from("world")]);
+        assert_eq!(w.to_string(), "[hello, world]");
+    }
+
+    #[test]
+    fn wrapper_delegates_to_the_vec() {
+        let mut w: Wrapper = ["a", "b"].iter().map(|s| s.to_string()).collect();
+        w.push(String::from("c"));
+        assert_eq!(w.pop(), Some(String::from("c")));
+        assert_eq!(w.len(), 2);
+        assert_eq!(w[1], "b");
+
+        w.extend(vec![String::from("d"), String::from("e")]);
+        for s in &mut w {
+            s.make_ascii_uppercase();
+        }
+        let lengths: usize = (&w).into_iter().map(|s| s.len()).sum();
+        assert_eq!(lengths, 4);
+        assert_eq!(w.to_string(), "[A, B, D, E]");
+
+        let owned: Vec<String> = w.clone().into_iter().rev().collect();
+        assert_eq!(owned, ["E", "D", "B", "A"]);
+        let back: Vec<String> = w.into();
+        assert_eq!(Wrapper::from(back.clone()).0, back);
+    }
+
+    #[test]
+    fn vec_wrapper_of_numbers() {
+        let mut w = VecWrapper::new();
+        assert_eq!(w.to_string(), "[]");
+        assert!(w.is_empty());
+
+        w.push(1);
+        assert_eq!(w.to_string(), "[1]");
+        w.push(-2);
+        assert_eq!(w.to_string(), "[1, -2]");
+        assert_eq!(w.len(), 2);
+    }
+
+    #[test]
+    fn vec_wrapper_of_strings() {
+        let mut w: VecWrapper<String> = VecWrapper::default();
+        assert_eq!(w.to_string(), "[]");
+
+        w.push(String::from("hello"));
+        w.push(String::from("world"));
+
+        assert_eq!(w.to_string(), "[hello, world]");
+        assert_eq!(w.to_string(), Wrapper(w.0.clone()).to_string());
+        assert_eq!(Vec::from(w).len(), 2);
+    }
+
+    #[test]
+    fn wrapper_index_reads_and_writes() {
+        let mut w = Wrapper(vec![String::from("a"), String::from("b")]);
+
+        assert_eq!(w[0], "a");
+        w[1].push('!');
+        w[0] = String::from("z");
+
+        assert_eq!(w.to_string(), "[z, b!]");
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 2 but the index is 2")]
+    fn wrapper_index_out_of_bounds_panics_like_vec() {
+        let mut w = Wrapper(vec![String::from("a"), String::from("b")]);
+        w[2].push('!');
+    }
+
+    #[test]
+    fn wrapper_is_found_by_slice() {
+        use std::collections::HashSet;
+        use std::hash::{BuildHasher, RandomState};
+
+        fn total_len(strings: impl AsRef<[String]>) -> usize {
+            strings.as_ref().iter().map(String::len).sum()
+        }
+
+        let strings = vec![String::from("ab"), String::from("c")];
+        let w = Wrapper(strings.clone());
+        assert_eq!(total_len(&w), 3);
+
+        let set = HashSet::from([w.clone()]);
+        assert!(set.contains(&strings[..]));
+        assert!(!set.contains(&strings[..1]));
+
+        let state = RandomState::new();
+        assert_eq!(state.hash_one(&w), state.hash_one(&strings[..]));
+    }
+
+    #[test]
+    fn wrapper_works_as_a_map_key() {
+        let mut map = HashMap::new();
+        map.insert(Wrapper(vec![String::from("a")]), 1);
+        map.insert(Wrapper(vec![String::from("b"), String::from("c")]), 2);
+
+        let key = Wrapper(vec![String::from("b"), String::from("c")]);
+        assert_eq!(map.get(&key), Some(&2));
+        assert_eq!(map.get(&Wrapper(vec![])), None);
+    }
+
+    #[test]
+    fn gallery_shows_mixed_types_in_order() {
+        let mut gallery = Gallery::new();
+        assert!(gallery.is_empty());
+
+        gallery.add(Wrapper(vec![String::from("a"), String::from("b")]));
+        gallery.add(LenBox { len: 3 });
+        gallery.add(Point { x: -1, y: 4 });
+
+        assert_eq!(gallery.len(), 3);
+        assert_eq!(
+            gallery.show_all(),
+            [
+                "running show_stuff() [a, b]",
+                "running show_stuff() 3",
+                "running show_stuff() (-1, 4)",
+            ]
+        );
+    }
+
+    #[test]
+    fn upcast_formats_like_display() {
+        let point = Point { x: 1, y: -2 };
+        let wrapper = Wrapper(vec![String::from("a")]);
+        let items: Vec<Box<dyn ShowStuff>> =
+            vec![Box::new(point), Box::new(LenBox { len: 7 }), Box::new(wrapper.clone())];
+
+        assert_eq!(
+            print_all_displays(&items),
+            [point.to_string(), LenBox { len: 7 }.to_string(), wrapper.to_string()]
+        );
+        assert_eq!(items[2].as_ref().as_display().to_string(), "[a]");
+        assert!(print_all_displays(&[]).is_empty());
+    }
+
+    #[test]
+    fn gallery_uses_each_display_impl() {
+        let point = Point { x: 5, y: 6 };
+        let mut gallery = Gallery::new();
+        gallery.add(point);
+
+        assert_eq!(gallery.show_all()[0], format!("running show_stuff() {}", point));
+    }
+
+    fn strings(words: &[&str]) -> Vec<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn non_empty_wrapper_accepts_valid_strings() {
+        let wrapper = NonEmptyWrapper::try_from(strings(&["a", "b"])).unwrap();
+
+        assert_eq!(wrapper.len(), 2);
+        assert_eq!(wrapper[1], "b");
+        assert_eq!(Vec::from(wrapper), strings(&["a", "b"]));
+    }
+
+    #[test]
+    fn non_empty_wrapper_rejects_no_strings() {
+        assert_eq!(NonEmptyWrapper::try_from(Vec::new()), Err(NonEmptyError::NoStrings));
+        assert_eq!("".parse::<NonEmptyWrapper>(), Err(NonEmptyError::NoStrings));
+        assert_eq!("  ".parse::<NonEmptyWrapper>(), Err(NonEmptyError::NoStrings));
+    }
+
+    #[test]
+    fn non_empty_wrapper_reports_the_empty_index() {
+        let error = NonEmptyWrapper::try_from(strings(&["a", "b", "", ""])).unwrap_err();
+
+        assert_eq!(error, NonEmptyError::EmptyString { index: 2 });
+        assert_eq!(error.to_string(), "the string at index 2 is empty");
+        assert_eq!(
+            "a, ,c".parse::<NonEmptyWrapper>(),
+            Err(NonEmptyError::EmptyString { index: 1 })
+        );
+        assert_eq!("a,".parse::<NonEmptyWrapper>(), Err(NonEmptyError::EmptyString { index: 1 }));
+    }
+
+    #[test]
+    fn non_empty_wrapper_parse_round_trips() {
+        let wrapper: NonEmptyWrapper = "  one,two ,  three  ".parse().unwrap();
+
+        assert_eq!(*wrapper, strings(&["one", "two", "three"]));
+        assert_eq!(wrapper.to_string(), "one, two, three");
+        assert_eq!(wrapper.to_string().parse::<NonEmptyWrapper>(), Ok(wrapper));
+    }
+
+    #[test]
+    fn pretty_displays_with_alternate_debug() {
+        #[derive(Debug)]
+        #[allow(dead_code)]
+        struct Inner {
+            name: &'static str,
+            point: (i32, i32),
+        }
+
+        let pretty = prettify(Inner { name: "a", point: (1, 2) });
+
+        assert_eq!(
+            pretty.to_string(),
+            "Inner {\n    name: \"a\",\n    point: (\n        1,\n        2,\n    ),\n}"
+        );
+        assert_eq!(pretty.as_inner().name, "a");
+        assert_eq!(pretty.into_inner().point, (1, 2));
+    }
+
+    #[test]
+    fn pretty_compares_and_hashes_like_the_inner_value() {
+        let mut map = HashMap::new();
+        map.insert(Pretty::new(vec![1, 2]), "one two");
+        map.insert(prettify(vec![3]), "three");
+
+        assert_eq!(map.get(&Pretty::new(vec![1, 2])), Some(&"one two"));
+        assert_eq!(map.get(&Pretty::new(vec![2, 1])), None);
+        assert_eq!(Pretty::new("a"), Pretty::new("a"));
+        assert_ne!(Pretty::new("a"), Pretty::new("b"));
+    }
+
+    #[test]
+    fn cow_wrapper_displays_borrowed_strings() {
+        let w = CowWrapper::from(&["hello", "world"][..]);
+
+        assert!(w.0.iter().all(|string| matches!(string, Cow::Borrowed(_))));
+        assert_eq!(w.to_string(), "[hello, world]");
+    }
+
+    #[test]
+    fn cow_wrapper_displays_owned_strings() {
+        let w = CowWrapper::from(vec![String::from("a"), String::from("b")]);
+
+        assert!(w.0.iter().all(|string| matches!(string, Cow::Owned(_))));
+        assert_eq!(w.to_string(), "[a, b]");
+    }
+
+    #[test]
+    fn cow_wrapper_mixes_borrowed_and_owned() {
+        let mut w = CowWrapper::new();
+        assert_eq!(w.to_string(), "[]");
+
+        w.push("borrowed");
+        w.push(format!("owned {}", 1));
+        w.push("again");
+
+        assert!(matches!(w.0[0], Cow::Borrowed("borrowed")));
+        assert!(matches!(w.0[1], Cow::Owned(_)));
+        assert_eq!(w.to_string(), "[borrowed, owned 1, again]");
+    }
+}
@@ -0,0 +1,106 @@
+//Key and Value are associated types rather than generics, `trait Cache<K, V>`, because a cache
+// only ever stores one kind of key and one kind of value. With generics a single type could
+// implement Cache<u32, String> and Cache<String, u32> at the same time, and every call would
+// have to say which one it meant. As associated types they are fixed once per implementation.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+pub trait Cache {
+    type Key;
+    type Value;
+
+    fn get(&self, key: &Self::Key) -> Option<&Self::Value>;
+
+    fn put(&mut self, key: Self::Key, value: Self::Value);
+}
+
+//The generic parameters are on the struct, so HashMapCache<u32, String> is its own type with
+// exactly one Key and one Value.
+pub struct HashMapCache<K, V> {
+    map: HashMap<K, V>,
+}
+
+impl<K, V> HashMapCache<K, V> {
+    pub fn new() -> HashMapCache<K, V> {
+        HashMapCache {
+            map: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+impl<K, V> Default for HashMapCache<K, V> {
+    fn default() -> HashMapCache<K, V> {
+        HashMapCache::new()
+    }
+}
+
+impl<K: Eq + Hash, V> Cache for HashMapCache<K, V> {
+    type Key = K;
+    type Value = V;
+
+    fn get(&self, key: &K) -> Option<&V> {
+        self.map.get(key)
+    }
+
+    //Putting a key that is already there replaces its value.
+    fn put(&mut self, key: K, value: V) {
+        self.map.insert(key, value);
+    }
+}
+
+//Works with any cache, C::Key and C::Value name whatever types that cache was made for.
+pub fn get_or_put<C, F>(cache: &mut C, key: C::Key, make: F) -> &C::Value
+where
+    C: Cache,
+    C::Key: Clone,
+    F: FnOnce() -> C::Value,
+{
+    if cache.get(&key).is_none() {
+        cache.put(key.clone(), make());
+    }
+    cache.get(&key).expect("the value was just put into the cache")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn put_then_get() {
+        let mut cache = HashMapCache::new();
+        cache.put(1, String::from("one"));
+        cache.put(2, String::from("two"));
+        cache.put(1, String::from("uno"));
+
+        assert_eq!(cache.get(&1).map(String::as_str), Some("uno"));
+        assert_eq!(cache.get(&2).map(String::as_str), Some("two"));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn miss_is_none() {
+        let cache: HashMapCache<&str, u32> = HashMapCache::new();
+
+        assert!(cache.is_empty());
+        assert_eq!(cache.get(&"missing"), None);
+    }
+
+    #[test]
+    fn get_or_put_only_makes_missing_values() {
+        let mut cache = HashMapCache::new();
+        let mut made = 0;
+
+        assert_eq!(*get_or_put(&mut cache, "a", || { made += 1; 10 }), 10);
+        assert_eq!(*get_or_put(&mut cache, "a", || { made += 1; 20 }), 10);
+        assert_eq!(made, 1);
+    }
+}
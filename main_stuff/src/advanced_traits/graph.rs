@@ -0,0 +1,163 @@
+//A bigger associated types example than FooAssociated. A graph decides for itself how its nodes
+// are named and what its edges weigh, so NodeId and Weight are associated types. Code written
+// against `G: Graph` can pass G::NodeId values around without knowing they are usizes.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+pub trait Graph {
+    type NodeId;
+    type Weight;
+
+    fn add_node(&mut self) -> Self::NodeId;
+
+    //Edges are directed, from `from` to `to`.
+    fn add_edge(&mut self, from: Self::NodeId, to: Self::NodeId, weight: Self::Weight);
+
+    fn neighbors(&self, node: Self::NodeId) -> impl Iterator<Item = (Self::NodeId, Self::Weight)>;
+
+    //The total weight of the lightest path and the nodes along it, start and end included. None
+    // when there is no way to get from one to the other.
+    fn shortest_path(
+        &self,
+        from: Self::NodeId,
+        to: Self::NodeId,
+    ) -> Option<(Self::Weight, Vec<Self::NodeId>)>;
+}
+
+//Nodes are numbered from 0 in the order they are added, and each one keeps a list of its outgoing
+// edges.
+#[derive(Debug, Clone, Default)]
+pub struct AdjacencyList {
+    edges: Vec<Vec<(usize, u32)>>,
+}
+
+impl AdjacencyList {
+    pub fn new() -> AdjacencyList {
+        AdjacencyList::default()
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    fn check_node(&self, node: usize) {
+        assert!(node < self.edges.len(), "node {} does not exist", node);
+    }
+}
+
+impl Graph for AdjacencyList {
+    type NodeId = usize;
+    type Weight = u32;
+
+    fn add_node(&mut self) -> usize {
+        self.edges.push(Vec::new());
+        self.edges.len() - 1
+    }
+
+    //Panics if either node has not been added.
+    fn add_edge(&mut self, from: usize, to: usize, weight: u32) {
+        self.check_node(from);
+        self.check_node(to);
+        self.edges[from].push((to, weight));
+    }
+
+    fn neighbors(&self, node: usize) -> impl Iterator<Item = (usize, u32)> {
+        self.check_node(node);
+        self.edges[node].iter().copied()
+    }
+
+    //Dijkstra's algorithm. The heap always pops the closest node that has not been finished yet,
+    // and Reverse turns BinaryHeap's biggest-first into smallest-first. A node can be pushed more
+    // than once, so anything popped with a worse distance than the best known is skipped.
+    fn shortest_path(&self, from: usize, to: usize) -> Option<(u32, Vec<usize>)> {
+        self.check_node(from);
+        self.check_node(to);
+
+        let mut distance = vec![None; self.edges.len()];
+        let mut previous = vec![None; self.edges.len()];
+        let mut heap = BinaryHeap::new();
+        distance[from] = Some(0);
+        heap.push(Reverse((0_u32, from)));
+
+        while let Some(Reverse((cost, node))) = heap.pop() {
+            if node == to {
+                break;
+            }
+            if distance[node].is_some_and(|best| cost > best) {
+                continue;
+            }
+            for (next, weight) in self.neighbors(node) {
+                let next_cost = cost.saturating_add(weight);
+                if distance[next].is_none_or(|best| next_cost < best) {
+                    distance[next] = Some(next_cost);
+                    previous[next] = Some(node);
+                    heap.push(Reverse((next_cost, next)));
+                }
+            }
+        }
+
+        let total = distance[to]?;
+        let mut path = vec![to];
+        while let Some(node) = previous[*path.last().unwrap()] {
+            path.push(node);
+        }
+        path.reverse();
+        Some((total, path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    //0 -> 1 -> 3 is 1 + 5, 0 -> 2 -> 3 is 2 + 2, and 0 -> 1 -> 2 -> 3 is 1 + 1 + 2. Node 4 has
+    // an edge out but none in.
+    fn graph() -> AdjacencyList {
+        let mut graph = AdjacencyList::new();
+        for _ in 0..5 {
+            graph.add_node();
+        }
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(0, 2, 2);
+        graph.add_edge(1, 3, 5);
+        graph.add_edge(1, 2, 1);
+        graph.add_edge(2, 3, 2);
+        graph.add_edge(4, 0, 1);
+        graph
+    }
+
+    #[test]
+    fn neighbors_are_the_outgoing_edges() {
+        let graph = graph();
+
+        let neighbors: HashSet<(usize, u32)> = graph.neighbors(0).collect();
+        assert_eq!(neighbors, HashSet::from([(1, 1), (2, 2)]));
+        assert_eq!(graph.neighbors(3).count(), 0);
+        assert_eq!(graph.node_count(), 5);
+    }
+
+    #[test]
+    fn shortest_path_takes_the_lightest_route() {
+        let graph = graph();
+
+        assert_eq!(graph.shortest_path(0, 3), Some((4, vec![0, 2, 3])));
+        assert_eq!(graph.shortest_path(4, 3), Some((5, vec![4, 0, 2, 3])));
+        assert_eq!(graph.shortest_path(2, 2), Some((0, vec![2])));
+    }
+
+    #[test]
+    fn unreachable_node_has_no_path() {
+        let graph = graph();
+
+        assert_eq!(graph.shortest_path(3, 0), None);
+        assert_eq!(graph.shortest_path(0, 4), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "node 7 does not exist")]
+    fn edge_to_a_missing_node_panics() {
+        graph().add_edge(0, 7, 1);
+    }
+}
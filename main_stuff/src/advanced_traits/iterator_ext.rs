@@ -0,0 +1,175 @@
+//An extension trait adds methods to a trait that is not ours. IteratorExt has Iterator as a
+// supertrait and a blanket impl for every Iterator, so once it is in scope every iterator has
+// chunk_sums() and intersperse_item(). Each method just wraps the iterator in an adapter struct,
+// the same way map() and filter() do in the standard library, and the work happens lazily in the
+// adapter's next().
+
+use std::ops::Add;
+
+pub trait IteratorExt: Iterator {
+    //Sums every n items, with a shorter last chunk when the items do not divide evenly. Panics if
+    // n is 0.
+    fn chunk_sums(self, n: usize) -> ChunkSums<Self>
+    where
+        Self: Sized,
+        Self::Item: Add<Output = Self::Item> + Default,
+    {
+        assert!(n != 0, "chunk size must be non-zero");
+        ChunkSums { iter: self, n }
+    }
+
+    //Puts a copy of sep between each pair of items, but not before the first or after the last.
+    fn intersperse_item(self, sep: Self::Item) -> Intersperse<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        Intersperse {
+            iter: self,
+            sep,
+            started: false,
+            pending: None,
+        }
+    }
+}
+
+impl<I: Iterator> IteratorExt for I {}
+
+pub struct ChunkSums<I> {
+    iter: I,
+    n: usize,
+}
+
+impl<I> Iterator for ChunkSums<I>
+where
+    I: Iterator,
+    I::Item: Add<Output = I::Item> + Default,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let first = self.iter.next()?;
+        let rest = self.iter.by_ref().take(self.n - 1);
+        Some(rest.fold(I::Item::default() + first, |sum, item| sum + item))
+    }
+
+    //Every n items left over make one chunk, and any remainder makes one more.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (lower.div_ceil(self.n), upper.map(|upper| upper.div_ceil(self.n)))
+    }
+}
+
+//`started` is false until the first item has been handed out, since nothing goes before it.
+// After that each item from the iterator is held in `pending` while the separator goes first.
+pub struct Intersperse<I: Iterator> {
+    iter: I,
+    sep: I::Item,
+    started: bool,
+    pending: Option<I::Item>,
+}
+
+impl<I> Iterator for Intersperse<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        if let Some(item) = self.pending.take() {
+            return Some(item);
+        }
+        if !self.started {
+            self.started = true;
+            return self.iter.next();
+        }
+        let item = self.iter.next()?;
+        self.pending = Some(item);
+        Some(self.sep.clone())
+    }
+
+    //Before the first item, n items come out as 2n - 1. After it, every item left over comes out
+    // with a separator in front, plus the one that might already be pending.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let pending = usize::from(self.pending.is_some());
+        let count = |n: usize| -> Option<usize> {
+            if self.started {
+                n.checked_mul(2)?.checked_add(pending)
+            } else {
+                n.checked_mul(2).map(|doubled| doubled.saturating_sub(1))
+            }
+        };
+        (count(lower).unwrap_or(usize::MAX), upper.and_then(count))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunk_sums_with_even_chunks() {
+        let sums: Vec<i32> = (1..=6).chunk_sums(2).collect();
+
+        assert_eq!(sums, [3, 7, 11]);
+    }
+
+    #[test]
+    fn chunk_sums_with_a_partial_last_chunk() {
+        let sums: Vec<u32> = [1, 2, 3, 4, 5, 6, 7].into_iter().chunk_sums(3).collect();
+        assert_eq!(sums, [6, 15, 7]);
+
+        let sums: Vec<f64> = [0.5, 0.25].into_iter().chunk_sums(5).collect();
+        assert_eq!(sums, [0.75]);
+        assert_eq!(std::iter::empty::<i32>().chunk_sums(2).next(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size must be non-zero")]
+    fn chunk_sums_of_zero_panics() {
+        (1..3).chunk_sums(0);
+    }
+
+    #[test]
+    fn chunk_sums_size_hint() {
+        assert_eq!((0..7).chunk_sums(3).size_hint(), (3, Some(3)));
+        assert_eq!((0..6).chunk_sums(3).size_hint(), (2, Some(2)));
+        assert_eq!((0..0).chunk_sums(3).size_hint(), (0, Some(0)));
+        assert_eq!((0..10).filter(|n| n % 2 == 0).chunk_sums(4).size_hint(), (0, Some(3)));
+
+        let mut sums = (0..7).chunk_sums(3);
+        sums.next();
+        assert_eq!(sums.size_hint(), (2, Some(2)));
+        assert_eq!(sums.count(), 2);
+    }
+
+    #[test]
+    fn intersperse_between_items() {
+        let joined: String = "abc".chars().intersperse_item('-').collect();
+        assert_eq!(joined, "a-b-c");
+
+        let words: Vec<&str> = ["x", "y"].into_iter().intersperse_item(", ").collect();
+        assert_eq!(words, ["x", ", ", "y"]);
+    }
+
+    #[test]
+    fn intersperse_empty_and_single() {
+        assert_eq!(std::iter::empty::<u8>().intersperse_item(0).count(), 0);
+        assert_eq!(std::iter::once(5).intersperse_item(0).collect::<Vec<_>>(), [5]);
+    }
+
+    #[test]
+    fn intersperse_size_hint_matches_what_is_left() {
+        let mut interspersed = (1..=3).intersperse_item(0);
+
+        for expected in (0..=5).rev() {
+            assert_eq!(interspersed.size_hint(), (expected, Some(expected)));
+            interspersed.next();
+        }
+        assert_eq!(interspersed.next(), None);
+        assert_eq!((0..0).intersperse_item(9).size_hint(), (0, Some(0)));
+        assert_eq!((0..1).intersperse_item(9).size_hint(), (1, Some(1)));
+    }
+}
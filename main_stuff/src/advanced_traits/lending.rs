@@ -0,0 +1,201 @@
+//Iterator::next() takes `&mut self` but returns Self::Item, and Item cannot mention the lifetime
+// of that borrow. So an iterator can hand out references into something it borrows, but never
+// into itself. Overlapping &mut windows are the classic case where that is a problem. If two
+// windows could be alive at once they would both be able to change the same element, so each one
+// has to be given back before the next is handed out, which means borrowing from the iterator.
+//A generic associated type (GAT) fixes this by letting Item take a lifetime. Item<'a> is whatever
+// comes out of a `&'a mut self` borrow, and the `where Self: 'a` is required because the item can
+// only live as long as the iterator it came from. The catch is that this is not Iterator, so for
+// loops and the adapters from the standard library do not work on it.
+
+pub trait LendingIterator {
+    type Item<'a>
+    where
+        Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>>;
+
+    //The closure has to accept an item borrowed for any lifetime, which is what `for<'a>` says.
+    // The catch is that Item<'a> only exists when Self: 'a, and for any 'a at all that means
+    // Self: 'static. So this only works for iterators that do not borrow anything, such as
+    // LinesBuffered below. WindowsMut over a local slice has to use a `while let` loop instead.
+    // This is a known limitation of GATs that the compiler may lift one day.
+    fn for_each<F>(mut self, mut f: F)
+    where
+        Self: Sized,
+        F: for<'a> FnMut(Self::Item<'a>),
+    {
+        while let Some(item) = self.next() {
+            f(item);
+        }
+    }
+
+    fn count(mut self) -> usize
+    where
+        Self: Sized,
+    {
+        let mut count = 0;
+        while self.next().is_some() {
+            count += 1;
+        }
+        count
+    }
+}
+
+//Like slice::windows() but mutable. The slice has windows of `size` starting at 0, 1, 2 and so on,
+// so a slice of 5 has 3 windows of 3.
+pub struct WindowsMut<'s, T> {
+    slice: &'s mut [T],
+    size: usize,
+    start: usize,
+}
+
+impl<'s, T> WindowsMut<'s, T> {
+    //Panics if size is 0, the same as slice::windows().
+    pub fn new(slice: &'s mut [T], size: usize) -> WindowsMut<'s, T> {
+        assert!(size != 0, "window size must be non-zero");
+        WindowsMut { slice, size, start: 0 }
+    }
+}
+
+impl<T> LendingIterator for WindowsMut<'_, T> {
+    type Item<'a>
+        = &'a mut [T]
+    where
+        Self: 'a;
+
+    fn next(&mut self) -> Option<&mut [T]> {
+        let window = self.slice.get_mut(self.start..self.start + self.size)?;
+        self.start += 1;
+        Some(window)
+    }
+}
+
+//Goes through a String a line at a time, copying each line into the same buffer instead of making
+// a new String for every line. That only works because each line has to be given back before the
+// next one is copied in over it. Regular Iterator could not do this without allocating.
+pub struct LinesBuffered {
+    text: String,
+    position: usize,
+    buffer: String,
+}
+
+impl LinesBuffered {
+    pub fn new(text: String) -> LinesBuffered {
+        LinesBuffered {
+            text,
+            position: 0,
+            buffer: String::new(),
+        }
+    }
+
+    //How much room the buffer has, which only grows to fit the longest line so far.
+    pub fn capacity(&self) -> usize {
+        self.buffer.capacity()
+    }
+}
+
+impl LendingIterator for LinesBuffered {
+    type Item<'a>
+        = &'a str
+    where
+        Self: 'a;
+
+    //Lines end at "\n" or "\r\n", and neither ending ends up in the line, the same as str::lines().
+    fn next(&mut self) -> Option<&str> {
+        let rest = &self.text[self.position..];
+        if rest.is_empty() {
+            return None;
+        }
+        let line = match rest.find('\n') {
+            Some(end) => {
+                self.position += end + 1;
+                &rest[..end]
+            }
+            None => {
+                self.position = self.text.len();
+                rest
+            }
+        };
+
+        self.buffer.clear();
+        self.buffer.push_str(line.strip_suffix('\r').unwrap_or(line));
+        Some(&self.buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_window_can_be_changed() {
+        let mut numbers = [0; 5];
+
+        //Each window adds one to everything in it, so each element ends up counting how many
+        // windows it was part of.
+        let mut windows = WindowsMut::new(&mut numbers, 3);
+        while let Some(window) = windows.next() {
+            for n in window {
+                *n += 1;
+            }
+        }
+
+        assert_eq!(numbers, [1, 2, 3, 2, 1]);
+    }
+
+    #[test]
+    fn windows_see_earlier_changes() {
+        let mut numbers = [1, 1, 0, 0, 0, 0];
+
+        //A running Fibonacci, each window fills in its last slot from the two before it.
+        let mut windows = WindowsMut::new(&mut numbers, 3);
+        while let Some(window) = windows.next() {
+            window[2] = window[0] + window[1];
+        }
+
+        assert_eq!(numbers, [1, 1, 2, 3, 5, 8]);
+    }
+
+    #[test]
+    fn window_counts() {
+        let mut numbers = [0; 4];
+
+        assert_eq!(WindowsMut::new(&mut numbers, 1).count(), 4);
+        assert_eq!(WindowsMut::new(&mut numbers, 2).count(), 3);
+        assert_eq!(WindowsMut::new(&mut numbers, 4).count(), 1);
+        assert_eq!(WindowsMut::new(&mut numbers, 5).count(), 0);
+        assert_eq!(WindowsMut::new(&mut [0_u8; 0], 1).count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "window size must be non-zero")]
+    fn zero_size_windows_panic() {
+        WindowsMut::new(&mut [1, 2], 0);
+    }
+
+    #[test]
+    fn lines_reuse_one_buffer() {
+        let text = String::from("a fairly long first line\r\nb\n\nlast");
+        let mut lines = LinesBuffered::new(text);
+        let mut seen = Vec::new();
+
+        while let Some(line) = lines.next() {
+            seen.push(line.to_string());
+        }
+
+        assert_eq!(seen, ["a fairly long first line", "b", "", "last"]);
+        assert!(lines.capacity() >= "a fairly long first line".len());
+        assert_eq!(LinesBuffered::new(String::from("one\ntwo\n")).count(), 2);
+        assert_eq!(LinesBuffered::new(String::new()).count(), 0);
+    }
+
+    #[test]
+    fn for_each_over_an_owning_iterator() {
+        let mut lengths = Vec::new();
+
+        LinesBuffered::new(String::from("ab\nc\n\ndef")).for_each(|line| lengths.push(line.len()));
+
+        assert_eq!(lengths, [2, 1, 0, 3]);
+    }
+}
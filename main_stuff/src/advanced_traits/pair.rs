@@ -0,0 +1,53 @@
+//Another associated type example. Zip turns a pair of collections into a Vec of pairs, and the
+// types that come out are decided by each implementation through A and B. Calling zip() never
+// needs to name them, `Pair(vec![1], vec!["a"]).zip()` already knows it gives Vec<(i32, &str)>.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pair<A, B>(pub A, pub B);
+
+pub trait Zip {
+    type A;
+    type B;
+
+    fn zip(self) -> Vec<(Self::A, Self::B)>;
+}
+
+//Iterator::zip() stops at whichever side runs out first, so anything left over on the longer
+// side is dropped.
+impl<A, B> Zip for Pair<Vec<A>, Vec<B>> {
+    type A = A;
+    type B = B;
+
+    fn zip(self) -> Vec<(A, B)> {
+        self.0.into_iter().zip(self.1).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zips_equal_lengths() {
+        let pair = Pair(vec![1, 2, 3], vec!["a", "b", "c"]);
+
+        assert_eq!(pair.zip(), [(1, "a"), (2, "b"), (3, "c")]);
+    }
+
+    #[test]
+    fn zips_to_the_shorter_length() {
+        assert_eq!(Pair(vec![1, 2, 3], vec!['x']).zip(), [(1, 'x')]);
+        assert_eq!(Pair(vec![1], vec![true, false]).zip(), [(1, true)]);
+        assert_eq!(Pair(Vec::<u8>::new(), vec![1.5]).zip(), []);
+    }
+
+    #[test]
+    fn zipped_types_come_from_the_associated_types() {
+        type Numbers = Pair<Vec<u8>, Vec<String>>;
+
+        let zipped: Vec<(<Numbers as Zip>::A, <Numbers as Zip>::B)> =
+            Pair(vec![7_u8], vec![String::from("seven")]).zip();
+
+        assert_eq!(zipped, [(7, String::from("seven"))]);
+    }
+}
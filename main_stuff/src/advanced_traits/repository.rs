@@ -0,0 +1,182 @@
+//Where BarStruct implements FooGeneric twice to show that it can, this is a case where only
+// associated types make sense. A repository stores one kind of key and one kind of value, and
+// code using it only needs to say `R: Repository` and then talk about R::Key and R::Value.
+//LoggedRepo shows why that matters. It wraps any Repository at all and is one itself, with the
+// same Key and Value as the one inside. With generics it would need to carry K and V around as
+// extra parameters, LoggedRepo<R, K, V>, that only ever repeat what R already says.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::mem;
+
+pub trait Repository {
+    type Key;
+    type Value;
+
+    //Returns the value that was replaced, if there was one.
+    fn insert(&mut self, key: Self::Key, value: Self::Value) -> Option<Self::Value>;
+
+    fn get(&self, key: &Self::Key) -> Option<&Self::Value>;
+
+    fn remove(&mut self, key: &Self::Key) -> Option<Self::Value>;
+
+    fn all(&self) -> Vec<(&Self::Key, &Self::Value)>;
+}
+
+#[derive(Debug, Clone)]
+pub struct InMemoryRepo<K, V>(HashMap<K, V>);
+
+impl<K, V> InMemoryRepo<K, V> {
+    pub fn new() -> InMemoryRepo<K, V> {
+        InMemoryRepo(HashMap::new())
+    }
+}
+
+impl<K, V> Default for InMemoryRepo<K, V> {
+    fn default() -> InMemoryRepo<K, V> {
+        InMemoryRepo::new()
+    }
+}
+
+impl<K: Eq + Hash + Ord, V> Repository for InMemoryRepo<K, V> {
+    type Key = K;
+    type Value = V;
+
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.0.insert(key, value)
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        self.0.get(key)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        self.0.remove(key)
+    }
+
+    //Sorted by key, since a HashMap has no order of its own.
+    fn all(&self) -> Vec<(&K, &V)> {
+        let mut all: Vec<(&K, &V)> = self.0.iter().collect();
+        all.sort_by(|a, b| a.0.cmp(b.0));
+        all
+    }
+}
+
+//Each call that went through a LoggedRepo, and whether it found anything.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operation<K> {
+    Insert { key: K, replaced: bool },
+    Get { key: K, found: bool },
+    Remove { key: K, found: bool },
+    All { count: usize },
+}
+
+//get() and all() only take &self, so the log is in a RefCell to be able to record them anyway.
+pub struct LoggedRepo<R: Repository> {
+    repo: R,
+    log: RefCell<Vec<Operation<R::Key>>>,
+}
+
+impl<R: Repository> LoggedRepo<R> {
+    pub fn new(repo: R) -> LoggedRepo<R> {
+        LoggedRepo {
+            repo,
+            log: RefCell::new(Vec::new()),
+        }
+    }
+
+    //Hands back everything logged so far and starts a fresh log.
+    pub fn take_log(&mut self) -> Vec<Operation<R::Key>> {
+        mem::take(self.log.get_mut())
+    }
+
+    pub fn into_inner(self) -> R {
+        self.repo
+    }
+}
+
+impl<R> Repository for LoggedRepo<R>
+where
+    R: Repository,
+    R::Key: Clone,
+{
+    type Key = R::Key;
+    type Value = R::Value;
+
+    fn insert(&mut self, key: R::Key, value: R::Value) -> Option<R::Value> {
+        let replaced = self.repo.insert(key.clone(), value);
+        self.log.get_mut().push(Operation::Insert { key, replaced: replaced.is_some() });
+        replaced
+    }
+
+    fn get(&self, key: &R::Key) -> Option<&R::Value> {
+        let value = self.repo.get(key);
+        self.log.borrow_mut().push(Operation::Get { key: key.clone(), found: value.is_some() });
+        value
+    }
+
+    fn remove(&mut self, key: &R::Key) -> Option<R::Value> {
+        let value = self.repo.remove(key);
+        self.log.get_mut().push(Operation::Remove { key: key.clone(), found: value.is_some() });
+        value
+    }
+
+    fn all(&self) -> Vec<(&R::Key, &R::Value)> {
+        let all = self.repo.all();
+        self.log.borrow_mut().push(Operation::All { count: all.len() });
+        all
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //The same steps against any repository, returning what all() saw at the end.
+    fn scenario<R>(repo: &mut R) -> Vec<(u32, String)>
+    where
+        R: Repository<Key = u32, Value = String>,
+    {
+        assert_eq!(repo.insert(2, String::from("two")), None);
+        assert_eq!(repo.insert(1, String::from("one")), None);
+        assert_eq!(repo.insert(2, String::from("TWO")), Some(String::from("two")));
+        assert_eq!(repo.get(&2).map(String::as_str), Some("TWO"));
+        assert_eq!(repo.get(&3), None);
+        assert_eq!(repo.remove(&1), Some(String::from("one")));
+        assert_eq!(repo.remove(&1), None);
+        repo.insert(5, String::from("five"));
+
+        repo.all().into_iter().map(|(key, value)| (*key, value.clone())).collect()
+    }
+
+    #[test]
+    fn in_memory_repo() {
+        let mut repo = InMemoryRepo::new();
+
+        assert_eq!(scenario(&mut repo), [(2, String::from("TWO")), (5, String::from("five"))]);
+    }
+
+    #[test]
+    fn logged_repo_has_the_same_data_and_a_log() {
+        let mut repo = LoggedRepo::new(InMemoryRepo::new());
+
+        assert_eq!(scenario(&mut repo), [(2, String::from("TWO")), (5, String::from("five"))]);
+        assert_eq!(
+            repo.take_log(),
+            [
+                Operation::Insert { key: 2, replaced: false },
+                Operation::Insert { key: 1, replaced: false },
+                Operation::Insert { key: 2, replaced: true },
+                Operation::Get { key: 2, found: true },
+                Operation::Get { key: 3, found: false },
+                Operation::Remove { key: 1, found: true },
+                Operation::Remove { key: 1, found: false },
+                Operation::Insert { key: 5, replaced: false },
+                Operation::All { count: 2 },
+            ]
+        );
+        assert!(repo.take_log().is_empty());
+        assert_eq!(repo.into_inner().all().len(), 2);
+    }
+}
@@ -0,0 +1,115 @@
+//A supertrait does not have to be public. Summary requires private::Sealed, and since the private
+// module cannot be reached from outside of this file, no other code can implement Sealed and so
+// no other code can implement Summary either. Everyone can still use Summary, call its methods
+// and take it as a bound, which leaves this module free to add methods to it later without
+// breaking some implementation it does not know about.
+//SummaryExt goes the other way. It is public and open, but every one of its methods has a default
+// and it is implemented for every Summary by the blanket impl, so there is never any reason to
+// implement it by hand.
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// A short summary of a piece of content. This trait is sealed, so it cannot be implemented
+/// outside of this module.
+///
+/// ```compile_fail
+/// use rust_book_chapter_19::advanced_traits::sealed::Summary;
+///
+/// struct Mine;
+///
+/// impl Summary for Mine {
+///     fn summarize(&self) -> String {
+///         String::from("mine")
+///     }
+/// }
+/// ```
+pub trait Summary: private::Sealed {
+    fn summarize(&self) -> String;
+}
+
+pub struct Tweet {
+    pub username: String,
+    pub content: String,
+}
+
+pub struct NewsArticle {
+    pub headline: String,
+    pub location: String,
+}
+
+impl private::Sealed for Tweet {}
+impl private::Sealed for NewsArticle {}
+
+impl Summary for Tweet {
+    fn summarize(&self) -> String {
+        format!("{}: {}", self.username, self.content)
+    }
+}
+
+impl Summary for NewsArticle {
+    fn summarize(&self) -> String {
+        format!("{}, {}", self.headline, self.location)
+    }
+}
+
+pub trait SummaryExt: Summary {
+    fn summary_uppercase(&self) -> String {
+        self.summarize().to_uppercase()
+    }
+
+    //Counted in chars, so something like "é" counts once.
+    fn summary_len(&self) -> usize {
+        self.summarize().chars().count()
+    }
+}
+
+//?Sized lets T be `dyn Summary` too, so the extension methods also work on a trait object.
+impl<T: Summary + ?Sized> SummaryExt for T {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tweet() -> Tweet {
+        Tweet {
+            username: String::from("horse_ebooks"),
+            content: String::from("of course"),
+        }
+    }
+
+    fn article() -> NewsArticle {
+        NewsArticle {
+            headline: String::from("Penguins win"),
+            location: String::from("Pittsburgh"),
+        }
+    }
+
+    #[test]
+    fn extension_methods_on_each_type() {
+        assert_eq!(tweet().summary_uppercase(), "HORSE_EBOOKS: OF COURSE");
+        assert_eq!(tweet().summary_len(), 23);
+        assert_eq!(article().summary_uppercase(), "PENGUINS WIN, PITTSBURGH");
+        assert_eq!(article().summary_len(), 24);
+    }
+
+    #[test]
+    fn extension_methods_on_trait_objects() {
+        let items: [&dyn Summary; 2] = [&tweet(), &article()];
+
+        let upper: Vec<String> = items.iter().map(|item| item.summary_uppercase()).collect();
+        assert_eq!(upper, ["HORSE_EBOOKS: OF COURSE", "PENGUINS WIN, PITTSBURGH"]);
+        assert_eq!(items.iter().map(|item| item.summary_len()).sum::<usize>(), 47);
+    }
+
+    #[test]
+    fn summary_len_counts_chars() {
+        let tweet = Tweet {
+            username: String::from("é"),
+            content: String::from("à"),
+        };
+
+        assert_eq!(tweet.summary_len(), 4);
+    }
+}
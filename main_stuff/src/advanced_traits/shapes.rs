@@ -0,0 +1,108 @@
+//A trait can be used as a type through `dyn Trait`. This is called a trait object and the method
+// that gets called is looked up at runtime (dynamic dispatch) instead of being picked by the
+// compiler. In order for this to work the trait must be object safe, which roughly means that
+// none of its methods are generic and they all take `self` in some form.
+
+use std::f64::consts::PI;
+
+pub trait Shape {
+    fn area(&self) -> f64;
+
+    fn name(&self) -> &str;
+}
+
+pub struct Circle {
+    pub radius: f64,
+}
+
+impl Shape for Circle {
+    fn area(&self) -> f64 {
+        PI * self.radius * self.radius
+    }
+
+    fn name(&self) -> &str {
+        "circle"
+    }
+}
+
+pub struct Rectangle {
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Shape for Rectangle {
+    fn area(&self) -> f64 {
+        self.width * self.height
+    }
+
+    fn name(&self) -> &str {
+        "rectangle"
+    }
+}
+
+pub struct Square {
+    pub side: f64,
+}
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.side * self.side
+    }
+
+    fn name(&self) -> &str {
+        "square"
+    }
+}
+
+pub struct Triangle {
+    pub base: f64,
+    pub height: f64,
+}
+
+impl Shape for Triangle {
+    fn area(&self) -> f64 {
+        0.5 * self.base * self.height
+    }
+
+    fn name(&self) -> &str {
+        "triangle"
+    }
+}
+
+//Different shapes can be stored in the same slice because they are all behind a Box<dyn Shape>.
+pub fn total_area(shapes: &[Box<dyn Shape>]) -> f64 {
+    shapes.iter().map(|shape| shape.area()).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn total_area_of_mixed_shapes() {
+        let shapes: Vec<Box<dyn Shape>> = vec![
+            Box::new(Circle { radius: 1.0 }),
+            Box::new(Rectangle { width: 2.0, height: 3.0 }),
+            Box::new(Circle { radius: 2.0 }),
+        ];
+
+        let expected = PI + 6.0 + 4.0 * PI;
+        assert!((total_area(&shapes) - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn names_are_dispatched_dynamically() {
+        let shapes: Vec<Box<dyn Shape>> = vec![
+            Box::new(Rectangle { width: 1.0, height: 1.0 }),
+            Box::new(Circle { radius: 1.0 }),
+        ];
+
+        let names: Vec<&str> = shapes.iter().map(|shape| shape.name()).collect();
+        assert_eq!(names, ["rectangle", "circle"]);
+    }
+
+    #[test]
+    fn total_area_of_nothing_is_zero() {
+        assert_eq!(total_area(&[]), 0.0);
+    }
+}
@@ -0,0 +1,120 @@
+//Shapes again, this time with associated constants. A const is part of the type rather than of any
+// one value, which is exactly why a trait with one cannot be made into a trait object. A `dyn
+// SidedShape` has forgotten what type it is, so there would be no way to look up Self::SIDES for
+// it. SidedShapeDyn below is the usual way around that, a second trait that only has methods,
+// implemented for every SidedShape by a blanket impl.
+//
+//The shapes themselves and their areas come from the shapes module. SidedShape only adds the
+// constant on top, through shapes::Shape as a supertrait.
+
+use crate::advanced_traits::shapes::{Circle, Rectangle, Shape, Square, Triangle};
+
+pub trait SidedShape: Shape {
+    const SIDES: u32;
+
+    fn describe(&self) -> String {
+        format!(
+            "a {} with {} sides and an area of {:.2}",
+            self.name(),
+            Self::SIDES,
+            self.area()
+        )
+    }
+}
+
+impl SidedShape for Square {
+    const SIDES: u32 = 4;
+}
+
+impl SidedShape for Rectangle {
+    const SIDES: u32 = 4;
+}
+
+impl SidedShape for Triangle {
+    const SIDES: u32 = 3;
+}
+
+//A circle has one curved edge and no straight ones. SIDES counts straight sides, so it is 0.
+impl SidedShape for Circle {
+    const SIDES: u32 = 0;
+}
+
+//Static dispatch, so every shape in the slice has to be the same type.
+pub fn total_area<S: SidedShape>(shapes: &[S]) -> f64 {
+    shapes.iter().map(|shape| shape.area()).sum()
+}
+
+pub trait SidedShapeDyn {
+    fn area_dyn(&self) -> f64;
+
+    fn describe_dyn(&self) -> String;
+}
+
+//Inside of the blanket impl S is still known, so the consts can be reached through it.
+impl<S: SidedShape> SidedShapeDyn for S {
+    fn area_dyn(&self) -> f64 {
+        self.area()
+    }
+
+    fn describe_dyn(&self) -> String {
+        self.describe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn areas() {
+        assert_eq!(Square { side: 3.0 }.area(), 9.0);
+        assert_eq!(Triangle { base: 4.0, height: 3.0 }.area(), 6.0);
+        assert!((Circle { radius: 2.0 }.area() - 4.0 * PI).abs() < 1e-9);
+    }
+
+    #[test]
+    fn describe_uses_the_constants() {
+        assert_eq!(Square { side: 2.0 }.describe(), "a square with 4 sides and an area of 4.00");
+        assert_eq!(
+            Triangle { base: 1.0, height: 1.0 }.describe(),
+            "a triangle with 3 sides and an area of 0.50"
+        );
+        assert_eq!(Circle { radius: 1.0 }.describe(), "a circle with 0 sides and an area of 3.14");
+        assert_eq!(
+            Rectangle { width: 2.0, height: 0.5 }.describe(),
+            "a rectangle with 4 sides and an area of 1.00"
+        );
+        assert_eq!(Square::SIDES + Triangle::SIDES + Circle::SIDES, 7);
+    }
+
+    #[test]
+    fn total_area_of_one_kind() {
+        let squares = [Square { side: 1.0 }, Square { side: 2.0 }];
+
+        assert_eq!(total_area(&squares), 5.0);
+        assert_eq!(total_area::<Circle>(&[]), 0.0);
+    }
+
+    #[test]
+    fn trait_objects_mix_shapes() {
+        let shapes: Vec<Box<dyn SidedShapeDyn>> = vec![
+            Box::new(Circle { radius: 1.0 }),
+            Box::new(Square { side: 2.0 }),
+            Box::new(Triangle { base: 2.0, height: 2.0 }),
+        ];
+
+        let descriptions: Vec<String> = shapes.iter().map(|shape| shape.describe_dyn()).collect();
+        assert_eq!(
+            descriptions,
+            [
+                "a circle with 0 sides and an area of 3.14",
+                "a square with 4 sides and an area of 4.00",
+                "a triangle with 3 sides and an area of 2.00",
+            ]
+        );
+
+        let total: f64 = shapes.iter().map(|shape| shape.area_dyn()).sum();
+        assert!((total - (PI + 6.0)).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,133 @@
+//A newtype over Vec that only lets things in and out of the top. Unlike Wrapper there is no Deref
+// to the Vec, since that would hand out insert() and remove() and the stack would be a stack in
+// name only. The iterators follow the same rule and go from the top down, the order pop() would
+// give the elements in.
+
+use std::iter::Rev;
+use std::slice;
+use std::vec;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stack<T>(Vec<T>);
+
+impl<T> Stack<T> {
+    pub fn new() -> Stack<T> {
+        Stack(Vec::new())
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.0.push(value);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.0.pop()
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.0.last()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> Rev<slice::Iter<'_, T>> {
+        self.into_iter()
+    }
+}
+
+impl<T> Default for Stack<T> {
+    fn default() -> Stack<T> {
+        Stack::new()
+    }
+}
+
+//The last element of the Vec is the top, so it becomes the top of the stack too.
+crate::impl_from_for_newtype!(<T> Stack(Vec<T>));
+
+impl<T> IntoIterator for Stack<T> {
+    type Item = T;
+    type IntoIter = Rev<vec::IntoIter<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter().rev()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Stack<T> {
+    type Item = &'a T;
+    type IntoIter = Rev<slice::Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter().rev()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stack() -> Stack<i32> {
+        let mut stack = Stack::new();
+        stack.push(1);
+        stack.push(2);
+        stack.push(3);
+        stack
+    }
+
+    #[test]
+    fn push_pop_and_peek() {
+        let mut stack = stack();
+
+        assert_eq!(stack.peek(), Some(&3));
+        assert_eq!(stack.pop(), Some(3));
+        assert_eq!(stack.len(), 2);
+        assert_eq!(stack.pop(), Some(2));
+        assert_eq!(stack.pop(), Some(1));
+        assert_eq!(stack.pop(), None);
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn by_value_iterates_in_pop_order() {
+        let mut popped = Vec::new();
+        let mut copy = stack();
+        while let Some(value) = copy.pop() {
+            popped.push(value);
+        }
+
+        let mut iterated = Vec::new();
+        for value in stack() {
+            iterated.push(value);
+        }
+
+        assert_eq!(iterated, popped);
+        assert_eq!(iterated, [3, 2, 1]);
+    }
+
+    #[test]
+    fn by_reference_leaves_the_stack_alone() {
+        let stack = stack();
+
+        let mut seen = Vec::new();
+        for value in &stack {
+            seen.push(*value);
+        }
+
+        assert_eq!(seen, [3, 2, 1]);
+        assert_eq!(stack.iter().copied().collect::<Vec<_>>(), [3, 2, 1]);
+        assert_eq!(stack.len(), 3);
+    }
+
+    #[test]
+    fn converts_from_and_into_a_vec() {
+        let stack = Stack::from(vec!['a', 'b']);
+
+        assert_eq!(stack.peek(), Some(&'b'));
+        assert_eq!(Vec::from(stack), ['a', 'b']);
+    }
+}
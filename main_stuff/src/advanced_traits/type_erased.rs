@@ -0,0 +1,69 @@
+//Everything else in this chapter works out types at compile time. std::any::Any is the exception,
+// it lets a value be stored with its type forgotten and then asked at runtime what it is. Each
+// 'static type has a unique TypeId, and a `dyn Any` carries the TypeId of the value inside, which
+// is what the downcasts compare against.
+//The 'static bound is there because lifetimes are erased before the program runs, so `&'a str`
+// and `&'b str` could not be told apart at runtime.
+
+use std::any::Any;
+
+pub struct TypeErased {
+    value: Box<dyn Any>,
+}
+
+impl TypeErased {
+    pub fn new<T: 'static>(val: T) -> TypeErased {
+        TypeErased {
+            value: Box::new(val),
+        }
+    }
+
+    pub fn is<T: 'static>(&self) -> bool {
+        self.value.is::<T>()
+    }
+
+    //Returns None when the stored value is not a T.
+    pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+        self.value.downcast_ref::<T>()
+    }
+
+    pub fn downcast_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        self.value.downcast_mut::<T>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn downcast_to_the_stored_type() {
+        let number = TypeErased::new(5_i32);
+        let text = TypeErased::new(String::from("hello"));
+
+        assert!(number.is::<i32>());
+        assert_eq!(number.downcast_ref::<i32>(), Some(&5));
+        assert!(text.is::<String>());
+        assert_eq!(text.downcast_ref::<String>().map(String::as_str), Some("hello"));
+    }
+
+    #[test]
+    fn wrong_type_is_none() {
+        let number = TypeErased::new(5_i32);
+
+        assert!(!number.is::<i64>());
+        assert_eq!(number.downcast_ref::<i64>(), None);
+        assert_eq!(number.downcast_ref::<String>(), None);
+    }
+
+    #[test]
+    fn mixed_values_in_one_collection() {
+        let mut values = [TypeErased::new(1_i32), TypeErased::new("two"), TypeErased::new(3_i32)];
+        if let Some(n) = values[2].downcast_mut::<i32>() {
+            *n *= 10;
+        }
+
+        let sum: i32 = values.iter().filter_map(|v| v.downcast_ref::<i32>()).sum();
+        assert_eq!(sum, 31);
+    }
+}
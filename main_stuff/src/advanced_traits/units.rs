@@ -0,0 +1,255 @@
+//The Rust Book example for default type parameters. Add is declared as `trait Add<Rhs = Self>`,
+// so `impl Add for Meters` adds Meters to Meters, while `impl Add<Meters> for Millimeters` picks
+// a different right hand side. Each unit is a newtype around a u64, so the compiler will not let
+// a length in meters be mixed up with one in millimeters by accident.
+//
+//When two different units are added, the result is in the smaller one so that nothing is lost.
+// Conversions go through TryFrom in both directions. Going from a bigger unit to a smaller one is
+// exact but can be too many to fit in a u64, and going the other way only works for whole
+// numbers of the bigger unit.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::ops::Add;
+
+//Returned when a length does not come out to a whole number of the bigger unit. The length is
+// kept in the unit it was given in, since multiplying it out to millimeters could overflow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotWholeUnits {
+    pub value: u64,
+    pub suffix: &'static str,
+    pub unit: &'static str,
+}
+
+impl Display for NotWholeUnits {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} is not a whole number of {}", self.value, self.suffix, self.unit)
+    }
+}
+
+impl std::error::Error for NotWholeUnits {}
+
+//Returned when a length is too long to fit in a u64 of the smaller unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnitOverflow {
+    pub value: u64,
+    pub suffix: &'static str,
+    pub unit: &'static str,
+}
+
+impl Display for UnitOverflow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} is too long to fit in {}", self.value, self.suffix, self.unit)
+    }
+}
+
+impl std::error::Error for UnitOverflow {}
+
+macro_rules! unit {
+    ( $name:ident, $suffix:literal, $millimeters:expr ) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(pub u64);
+
+        impl $name {
+            //How many millimeters are in one of this unit.
+            const MILLIMETERS: u64 = $millimeters;
+            const SUFFIX: &'static str = $suffix;
+
+            //u128 so that comparing a huge number of kilometers cannot overflow.
+            fn in_millimeters(self) -> u128 {
+                self.0 as u128 * Self::MILLIMETERS as u128
+            }
+        }
+
+        impl Add for $name {
+            type Output = $name;
+
+            fn add(self, other: $name) -> $name {
+                $name(self.0 + other.0)
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                write!(f, "{} {}", self.0, Self::SUFFIX)
+            }
+        }
+    };
+}
+
+//Everything between a smaller unit and a bigger one.
+macro_rules! between {
+    ( $small:ident, $big:ident ) => {
+        impl TryFrom<$big> for $small {
+            type Error = UnitOverflow;
+
+            fn try_from(big: $big) -> Result<$small, UnitOverflow> {
+                match big.0.checked_mul($big::MILLIMETERS / $small::MILLIMETERS) {
+                    Some(value) => Ok($small(value)),
+                    None => Err(UnitOverflow {
+                        value: big.0,
+                        suffix: $big::SUFFIX,
+                        unit: stringify!($small),
+                    }),
+                }
+            }
+        }
+
+        impl TryFrom<$small> for $big {
+            type Error = NotWholeUnits;
+
+            fn try_from(small: $small) -> Result<$big, NotWholeUnits> {
+                let per = $big::MILLIMETERS / $small::MILLIMETERS;
+                if small.0 % per != 0 {
+                    return Err(NotWholeUnits {
+                        value: small.0,
+                        suffix: $small::SUFFIX,
+                        unit: stringify!($big),
+                    });
+                }
+                Ok($big(small.0 / per))
+            }
+        }
+
+        //A sum that does not fit panics, the same as adding two u64s that overflow in a debug
+        // build.
+        impl Add<$big> for $small {
+            type Output = $small;
+
+            fn add(self, other: $big) -> $small {
+                match $small::try_from(other) {
+                    Ok(other) => self + other,
+                    Err(error) => panic!("{}", error),
+                }
+            }
+        }
+
+        impl Add<$small> for $big {
+            type Output = $small;
+
+            fn add(self, other: $small) -> $small {
+                other + self
+            }
+        }
+
+        impl PartialEq<$big> for $small {
+            fn eq(&self, other: &$big) -> bool {
+                self.in_millimeters() == other.in_millimeters()
+            }
+        }
+
+        impl PartialEq<$small> for $big {
+            fn eq(&self, other: &$small) -> bool {
+                self.in_millimeters() == other.in_millimeters()
+            }
+        }
+
+        impl PartialOrd<$big> for $small {
+            fn partial_cmp(&self, other: &$big) -> Option<Ordering> {
+                self.in_millimeters().partial_cmp(&other.in_millimeters())
+            }
+        }
+
+        impl PartialOrd<$small> for $big {
+            fn partial_cmp(&self, other: &$small) -> Option<Ordering> {
+                self.in_millimeters().partial_cmp(&other.in_millimeters())
+            }
+        }
+    };
+}
+
+unit!(Millimeters, "mm", 1);
+unit!(Meters, "m", 1_000);
+unit!(Kilometers, "km", 1_000_000);
+
+between!(Millimeters, Meters);
+between!(Millimeters, Kilometers);
+between!(Meters, Kilometers);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn millimeters_plus_meters() {
+        assert_eq!(Millimeters(500) + Meters(2), Millimeters(2_500));
+        assert_eq!(Meters(2) + Millimeters(500), Millimeters(2_500));
+        assert_eq!(Millimeters(1) + Millimeters(2), Millimeters(3));
+    }
+
+    #[test]
+    fn meters_plus_kilometers() {
+        assert_eq!(Meters(250) + Kilometers(3), Meters(3_250));
+        assert_eq!(Kilometers(1) + Kilometers(1), Kilometers(2));
+        assert_eq!(Kilometers(1) + Millimeters(1), Millimeters(1_000_001));
+    }
+
+    #[test]
+    fn ordering_across_units() {
+        assert!(Millimeters(999) < Meters(1));
+        assert!(Meters(1_001) > Kilometers(1));
+        assert!(Kilometers(2) > Millimeters(1_999_999));
+        assert_eq!(Meters(3_000), Kilometers(3));
+        assert_eq!(Millimeters(5_000), Meters(5));
+        assert!(Kilometers(u64::MAX) > Millimeters(u64::MAX));
+    }
+
+    #[test]
+    fn conversions_round_trip() {
+        for km in [0, 1, 7, 12_345] {
+            let meters = Meters::try_from(Kilometers(km)).unwrap();
+            let millimeters = Millimeters::try_from(meters).unwrap();
+
+            assert_eq!(Kilometers::try_from(meters), Ok(Kilometers(km)));
+            assert_eq!(Kilometers::try_from(millimeters), Ok(Kilometers(km)));
+            assert_eq!(Meters::try_from(millimeters), Ok(meters));
+        }
+    }
+
+    #[test]
+    fn partial_units_do_not_convert_up() {
+        let error = Meters::try_from(Millimeters(1_500)).unwrap_err();
+
+        assert_eq!(error, NotWholeUnits { value: 1_500, suffix: "mm", unit: "Meters" });
+        assert_eq!(error.to_string(), "1500 mm is not a whole number of Meters");
+    }
+
+    #[test]
+    fn conversions_at_u64_max() {
+        assert_eq!(
+            Millimeters::try_from(Kilometers(u64::MAX)),
+            Err(UnitOverflow { value: u64::MAX, suffix: "km", unit: "Millimeters" })
+        );
+        assert_eq!(
+            Meters::try_from(Kilometers(u64::MAX)).unwrap_err().to_string(),
+            "18446744073709551615 km is too long to fit in Meters"
+        );
+        assert_eq!(
+            Millimeters::try_from(Kilometers(u64::MAX / 1_000_000)),
+            Ok(Millimeters(u64::MAX / 1_000_000 * 1_000_000))
+        );
+
+        assert_eq!(
+            Meters::try_from(Millimeters(u64::MAX)),
+            Err(NotWholeUnits { value: u64::MAX, suffix: "mm", unit: "Meters" })
+        );
+        assert_eq!(
+            Kilometers::try_from(Meters(u64::MAX)).unwrap_err().to_string(),
+            "18446744073709551615 m is not a whole number of Kilometers"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "18446744073709551615 km is too long to fit in Meters")]
+    fn adding_a_length_that_does_not_fit_panics() {
+        let _ = Meters(1) + Kilometers(u64::MAX);
+    }
+
+    #[test]
+    fn display_has_the_suffix() {
+        assert_eq!(Millimeters(5).to_string(), "5 mm");
+        assert_eq!(Meters(3).to_string(), "3 m");
+        assert_eq!(Kilometers(2).to_string(), "2 km");
+    }
+}
@@ -0,0 +1,157 @@
+//What a `Box<dyn Shape>` looks like underneath, built by hand. A trait object is a fat pointer, one
+// pointer to the data and one to a vtable, which is a table of function pointers for each method
+// of the trait (plus one to drop the data). Calling a method looks the function up in the table
+// and passes it the data pointer. The data pointer has lost its type, so each function in the
+// table casts it back to the type that the table was made for.
+
+use crate::advanced_traits::shapes::{Circle, Rectangle, Shape};
+
+pub struct ShapeVtable {
+    pub area: fn(*const ()) -> f64,
+    pub name: fn(*const ()) -> &'static str,
+    pub drop: fn(*mut ()),
+}
+
+//The raw fat pointer. Nothing stops it from outliving its data, so it is only used inside of
+// ShapeBox, which owns the data it points to.
+#[derive(Clone, Copy)]
+pub struct DynShape {
+    data: *const (),
+    vtable: &'static ShapeVtable,
+}
+
+//Drops a value that was put on the heap with Box::new(). It is generic so that one can be made for
+// each type, which is what the compiler does for the drop entry of every real vtable.
+fn drop_box<T>(data: *mut ()) {
+    //Safety: only used in vtables whose data pointer came from Box::into_raw() of a T.
+    unsafe {
+        drop(Box::from_raw(data as *mut T));
+    }
+}
+
+fn circle_area(data: *const ()) -> f64 {
+    //Safety: only used in CIRCLE_VTABLE, whose data pointer always points to a Circle.
+    let circle = unsafe { &*(data as *const Circle) };
+    Shape::area(circle)
+}
+
+fn rectangle_area(data: *const ()) -> f64 {
+    //Safety: only used in RECTANGLE_VTABLE, whose data pointer always points to a Rectangle.
+    let rectangle = unsafe { &*(data as *const Rectangle) };
+    Shape::area(rectangle)
+}
+
+//One table per type, shared by every value of that type, just like the compiler does it.
+static CIRCLE_VTABLE: ShapeVtable = ShapeVtable {
+    area: circle_area,
+    name: |_| "circle",
+    drop: drop_box::<Circle>,
+};
+
+static RECTANGLE_VTABLE: ShapeVtable = ShapeVtable {
+    area: rectangle_area,
+    name: |_| "rectangle",
+    drop: drop_box::<Rectangle>,
+};
+
+//An owned, hand made trait object. This is the safe wrapper, it is the only thing that can create
+// a DynShape, and it frees the data exactly once when it is dropped.
+pub struct ShapeBox {
+    raw: DynShape,
+}
+
+impl ShapeBox {
+    pub fn circle(radius: f64) -> ShapeBox {
+        let data = Box::into_raw(Box::new(Circle { radius })) as *const ();
+        //Safety: the data is a boxed Circle, which is what CIRCLE_VTABLE expects.
+        unsafe { ShapeBox::from_raw_parts(data, &CIRCLE_VTABLE) }
+    }
+
+    pub fn rectangle(width: f64, height: f64) -> ShapeBox {
+        let data = Box::into_raw(Box::new(Rectangle { width, height })) as *const ();
+        //Safety: the data is a boxed Rectangle, which is what RECTANGLE_VTABLE expects.
+        unsafe { ShapeBox::from_raw_parts(data, &RECTANGLE_VTABLE) }
+    }
+
+    /// Puts a data pointer and a vtable together into an owned trait object.
+    ///
+    /// # Safety
+    ///
+    /// Every function in `vtable` must be fine to call with `data`, and `vtable.drop` must free
+    /// it. The ShapeBox takes ownership of `data`, so nothing else may use or free it afterwards.
+    pub unsafe fn from_raw_parts(data: *const (), vtable: &'static ShapeVtable) -> ShapeBox {
+        ShapeBox {
+            raw: DynShape { data, vtable },
+        }
+    }
+
+    //Dynamic dispatch by hand, look up the function and pass it the data.
+    pub fn area(&self) -> f64 {
+        (self.raw.vtable.area)(self.raw.data)
+    }
+
+    pub fn name(&self) -> &'static str {
+        (self.raw.vtable.name)(self.raw.data)
+    }
+}
+
+impl Drop for ShapeBox {
+    fn drop(&mut self) {
+        (self.raw.vtable.drop)(self.raw.data as *mut ());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn matches_box_dyn_shape() {
+        let manual = [ShapeBox::circle(2.0), ShapeBox::rectangle(3.0, 4.0)];
+        let compiler: [Box<dyn Shape>; 2] = [
+            Box::new(Circle { radius: 2.0 }),
+            Box::new(Rectangle { width: 3.0, height: 4.0 }),
+        ];
+
+        for (manual, compiler) in manual.iter().zip(compiler.iter()) {
+            assert_eq!(manual.area(), compiler.area());
+            assert_eq!(manual.name(), compiler.name());
+        }
+        assert_eq!(manual[1].area(), 12.0);
+    }
+
+    #[test]
+    fn same_size_as_a_trait_object() {
+        assert_eq!(mem::size_of::<ShapeBox>(), mem::size_of::<Box<dyn Shape>>());
+    }
+
+    static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+    struct Tracked;
+
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            DROPS.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    static TRACKED_VTABLE: ShapeVtable = ShapeVtable {
+        area: |_| 1.0,
+        name: |_| "tracked",
+        drop: drop_box::<Tracked>,
+    };
+
+    #[test]
+    fn drop_runs_exactly_once() {
+        let data = Box::into_raw(Box::new(Tracked)) as *const ();
+        //Safety: the data is a boxed Tracked, which is what TRACKED_VTABLE frees.
+        let shape = unsafe { ShapeBox::from_raw_parts(data, &TRACKED_VTABLE) };
+        assert_eq!(shape.name(), "tracked");
+        assert_eq!(DROPS.load(Ordering::SeqCst), 0);
+
+        drop(shape);
+        assert_eq!(DROPS.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,128 @@
+use std::collections::{HashMap, HashSet};
+use std::mem;
+
+use crate::report::SectionReport;
+
+//More type aliases, for closures and for a module's Result, see aliases::Thunk and
+// aliases::Result.
+pub mod aliases;
+//Functions that work on unsized types, and unsized types behind smart pointers, see
+// dst::display_len and dst::StrInterner.
+pub mod dst;
+//A struct that gives the Hi alias below a real API, see nested_index::NestedIndex.
+pub mod nested_index;
+//The never type coercing into other types, see never::choose.
+pub mod never;
+//A newtype that gives a HashMap a more conceptual API, see phone_book::PhoneBook.
+pub mod phone_book;
+//A blog post whose state is a zero sized type parameter, see post::Post.
+pub mod post;
+//Sizes and alignments worked out at compile time, see sizing::SizeFacts.
+pub mod sizing;
+//A newtype for each kind of id so they cannot be mixed up, see typed_ids::Store.
+pub mod typed_ids;
+
+//The newtype pattern can also be used to hide implementation details. For example a HashMap
+// could have a Wrapper that makes the API for it more conceptual, which is what
+// phone_book::PhoneBook does.
+
+//There is something similar to typedef in C++ that can be done to alias types. It seems to
+// mostly be used to shorten long type names.
+pub type Hi = HashMap<Vec<i32>, HashMap<Vec<String>, HashSet<u32>>>;
+
+//The book's other examples of aliases are in aliases.
+
+//As a fun note, the reason the namespace can be eliminated is because of type aliasing inside
+// the standard library. For example, type HashMap<T, U> = std::HashMap<T, U>.
+
+//There is a `never type` that is returned as shown below in foo. This means that a type is
+// never returned from this. So for example things link `continue` and `panic!` return this
+// type. This allows for types to be properly be determined inside things like match statements.
+pub fn foo() -> ! {
+    panic!("never type");
+}
+
+//never::choose() and never::serve() show where this coercion is actually useful.
+
+//Essentially rust stores both the memory address as well as the size of the memory when
+// handling dynamic memory. This seems to be done for support for slicing. For example, an &str
+// type does this because the size is unknown until compile time. This is a bit different than
+// in C or C++, in those languages a reference is simply a pointer. Then either the size is
+// stored internally to the object or passed separately. Either way it must be handled manually.
+
+//Returns how many bytes a reference to the value takes up. For sized types this is a single
+// pointer, for unsized types such as str and slices it is the pointer plus the length.
+pub fn reference_size<T: ?Sized>(_value: &T) -> usize {
+    mem::size_of::<&T>()
+}
+
+//By nature, a generic type has the follow type automatically put on it by the compiler.
+pub fn generic<T: Sized>(_t: T) {
+    // --snip--
+}
+
+//This type can be overridden using the following method. The dst module has functions that put
+// this to use.
+pub fn generic_unsized<T: ?Sized>(_t: &T) {
+    // --snip--
+}
+
+pub fn run() -> SectionReport {
+    let mut report = SectionReport::new("types");
+
+    let hello: Hi = HashMap::new();
+
+    report.push(format!("hello: {:?}", hello));
+
+    //The same kind of data, but going through NestedIndex instead of the bare maps.
+    let mut index = nested_index::NestedIndex::new();
+    index.insert(&[1, 2], &[String::from("a")], 3);
+    index.insert(&[1, 2], &[String::from("a")], 3);
+    for (outer, inner, value) in index.entries() {
+        report.push(format!("index: {:?} {:?} {}", outer, inner, value));
+    }
+
+    //The never type in actual use, instead of only being declared by foo().
+    if let Some((number, attempts)) = never::first_number(&["one", "", "3"]) {
+        report.push(format!("parsed {} after {} attempts", number, attempts));
+    }
+    report.push(never::describe(never::Input::Number(7)));
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unsized_references_are_fat_pointers() {
+        let pointer = mem::size_of::<usize>();
+
+        assert_eq!(reference_size(&5_i32), pointer);
+        assert_eq!(reference_size("hello"), pointer * 2);
+        assert_eq!(reference_size(&[1, 2, 3][..]), pointer * 2);
+    }
+
+    #[test]
+    fn report_contains_key_lines() {
+        let report = run();
+
+        assert_eq!(report.name, "types");
+        assert_eq!(
+            report.lines,
+            vec![
+                "hello: {}",
+                r#"index: [1, 2] ["a"] 3"#,
+                "parsed 3 after 3 attempts",
+                "the number 7",
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "never type")]
+    fn foo_never_returns() {
+        foo();
+    }
+}
@@ -0,0 +1,185 @@
+//The rest of the type alias examples from the book. An alias is only another name, so Kilometers
+// below is still an i32 and the two can be added together freely. That is the difference from a
+// newtype like units::Kilometers, which would need an explicit conversion.
+
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::num::ParseIntError;
+
+use crate::advanced_types::phone_book::{PhoneNumber, PhoneNumberError};
+
+pub type Kilometers = i32;
+
+//The type a long signature like `Box<dyn Fn() + Send + 'static>` would otherwise be repeated in.
+pub type Thunk = Box<dyn Fn() + Send + 'static>;
+
+pub fn run_all(thunks: Vec<Thunk>) {
+    for thunk in thunks {
+        thunk();
+    }
+}
+
+//Collects thunks to run later, in the order they were pushed.
+#[derive(Default)]
+pub struct ThunkQueue {
+    thunks: Vec<Thunk>,
+}
+
+impl ThunkQueue {
+    pub fn new() -> ThunkQueue {
+        ThunkQueue::default()
+    }
+
+    pub fn push(&mut self, thunk: impl Fn() + Send + 'static) {
+        self.thunks.push(Box::new(thunk));
+    }
+
+    pub fn len(&self) -> usize {
+        self.thunks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.thunks.is_empty()
+    }
+
+    //Runs everything queued so far and leaves the queue empty.
+    pub fn drain(&mut self) {
+        run_all(std::mem::take(&mut self.thunks));
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DemoError {
+    MissingSeparator,
+    BadNumber(ParseIntError),
+    BadPhoneNumber(PhoneNumberError),
+}
+
+impl Display for DemoError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            DemoError::MissingSeparator => write!(f, "expected `name=number`"),
+            DemoError::BadNumber(error) => write!(f, "bad number: {}", error),
+            DemoError::BadPhoneNumber(error) => write!(f, "bad phone number: {}", error),
+        }
+    }
+}
+
+impl Error for DemoError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DemoError::MissingSeparator => None,
+            DemoError::BadNumber(error) => Some(error),
+            DemoError::BadPhoneNumber(error) => Some(error),
+        }
+    }
+}
+
+//These are what let `?` turn the underlying errors into a DemoError.
+impl From<ParseIntError> for DemoError {
+    fn from(error: ParseIntError) -> DemoError {
+        DemoError::BadNumber(error)
+    }
+}
+
+impl From<PhoneNumberError> for DemoError {
+    fn from(error: PhoneNumberError) -> DemoError {
+        DemoError::BadPhoneNumber(error)
+    }
+}
+
+//The same thing std::io does with io::Result. Every fallible function in here fails with a
+// DemoError, so the error type does not need to be written out each time.
+pub type Result<T> = std::result::Result<T, DemoError>;
+
+pub fn parse_distance(text: &str) -> Result<Kilometers> {
+    Ok(text.trim().parse()?)
+}
+
+//Parses "name=number" into the name and a checked PhoneNumber.
+pub fn parse_contact(line: &str) -> Result<(String, PhoneNumber)> {
+    let (name, number) = line.split_once('=').ok_or(DemoError::MissingSeparator)?;
+    Ok((name.trim().to_string(), PhoneNumber::new(number.trim())?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn aliases_are_the_same_type() {
+        let distance: Kilometers = 5;
+        let more: i32 = 3;
+
+        assert_eq!(distance + more, 8);
+    }
+
+    #[test]
+    fn queue_runs_thunks_in_order() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let count = Arc::new(AtomicUsize::new(0));
+        let mut queue = ThunkQueue::new();
+
+        let name = String::from("first");
+        let first_log = Arc::clone(&log);
+        queue.push(move || first_log.lock().unwrap().push(name.clone()));
+
+        let counter = Arc::clone(&count);
+        queue.push(move || {
+            counter.fetch_add(10, Ordering::SeqCst);
+        });
+
+        let second_log = Arc::clone(&log);
+        queue.push(move || second_log.lock().unwrap().push(String::from("third")));
+
+        assert_eq!(queue.len(), 3);
+        queue.drain();
+
+        assert!(queue.is_empty());
+        assert_eq!(*log.lock().unwrap(), ["first", "third"]);
+        assert_eq!(count.load(Ordering::SeqCst), 10);
+    }
+
+    #[test]
+    fn run_all_runs_every_thunk() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let thunks: Vec<Thunk> = (1..=3)
+            .map(|step| {
+                let count = Arc::clone(&count);
+                Box::new(move || {
+                    count.fetch_add(step, Ordering::SeqCst);
+                }) as Thunk
+            })
+            .collect();
+
+        run_all(thunks);
+
+        assert_eq!(count.load(Ordering::SeqCst), 6);
+    }
+
+    #[test]
+    fn parse_int_errors_convert() {
+        assert_eq!(parse_distance(" 42 "), Ok(42));
+
+        let error = parse_distance("far").unwrap_err();
+        assert!(matches!(error, DemoError::BadNumber(_)));
+        assert_eq!(error.to_string(), "bad number: invalid digit found in string");
+        assert!(error.source().is_some());
+    }
+
+    #[test]
+    fn phone_number_errors_convert() {
+        let (name, number) = parse_contact("Ferris = 5551234").unwrap();
+        assert_eq!(name, "Ferris");
+        assert_eq!(number.as_str(), "5551234");
+
+        assert_eq!(
+            parse_contact("Ferris=555"),
+            Err(DemoError::BadPhoneNumber(PhoneNumberError::TooShort { len: 3 }))
+        );
+        assert_eq!(parse_contact("Ferris 5551234"), Err(DemoError::MissingSeparator));
+    }
+}
@@ -0,0 +1,123 @@
+//Functions that take `T: ?Sized` so they work on str and slices themselves, not only on String
+// and Vec. T is always behind a reference here, and a reference to an unsized type is a fat
+// pointer that carries the length along, so the function can still work with it.
+//Unsized types can also live behind smart pointers. Box<str> and Rc<str> are a pointer plus a
+// length just like &str, but they own the text. Unlike String there is no spare capacity, so
+// they are a little smaller and cannot grow.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::fmt::{Display, Write};
+use std::rc::Rc;
+
+//Counts chars as they are written instead of building a String only to measure it.
+struct CharCounter(usize);
+
+impl Write for CharCounter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.chars().count();
+        Ok(())
+    }
+}
+
+//How many chars the value is when displayed. Without ?Sized, `display_len("abc")` would not
+// compile, since T would be str.
+pub fn display_len<T: ?Sized + Display>(value: &T) -> usize {
+    let mut counter = CharCounter(0);
+    write!(counter, "{}", value).expect("CharCounter never fails");
+    counter.0
+}
+
+pub fn boxed_clone_str(s: &str) -> Box<str> {
+    Box::from(s)
+}
+
+pub fn last_byte<T: ?Sized + AsRef<[u8]>>(value: &T) -> Option<u8> {
+    value.as_ref().last().copied()
+}
+
+//Keeps one copy of each distinct string. Interning the same text again hands out another Rc to
+// the copy that is already there, so equal strings share one allocation and can be compared by
+// pointer. The HashSet can be searched with a &str because Rc<str> implements Borrow<str>.
+#[derive(Debug, Default)]
+pub struct StrInterner {
+    strings: HashSet<Rc<str>>,
+}
+
+impl StrInterner {
+    pub fn new() -> StrInterner {
+        StrInterner::default()
+    }
+
+    pub fn intern(&mut self, s: &str) -> Rc<str> {
+        if let Some(existing) = self.strings.get(s) {
+            return Rc::clone(existing);
+        }
+        let interned: Rc<str> = Rc::from(s);
+        self.strings.insert(Rc::clone(&interned));
+        interned
+    }
+
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_len_of_sized_and_unsized() {
+        let owned = String::from("héllo");
+        let boxed = boxed_clone_str("boxed");
+
+        assert_eq!(display_len("abc"), 3);
+        assert_eq!(display_len(&owned), 5);
+        assert_eq!(display_len(&*boxed), 5);
+        assert_eq!(display_len(&boxed), 5);
+        assert_eq!(display_len(&-12), 3);
+        assert_eq!(display_len(""), 0);
+    }
+
+    #[test]
+    fn boxed_str_copies_the_text() {
+        let original = String::from("text");
+        let boxed = boxed_clone_str(&original);
+
+        assert_eq!(&*boxed, "text");
+        assert_ne!(boxed.as_ptr(), original.as_ptr());
+        assert!(boxed_clone_str("").is_empty());
+    }
+
+    #[test]
+    fn last_byte_of_strs_and_slices() {
+        assert_eq!(last_byte("abc"), Some(b'c'));
+        assert_eq!(last_byte(&String::from("xy")), Some(b'y'));
+        assert_eq!(last_byte(&*boxed_clone_str("z")), Some(b'z'));
+        assert_eq!(last_byte(&[1_u8, 2, 3][..]), Some(3));
+        assert_eq!(last_byte(""), None);
+        assert_eq!(last_byte(&Vec::<u8>::new()), None);
+    }
+
+    #[test]
+    fn interner_shares_equal_strings() {
+        let mut interner = StrInterner::new();
+        assert!(interner.is_empty());
+
+        let a = interner.intern("apple");
+        let b = interner.intern(&String::from("apple"));
+        let c = interner.intern("banana");
+        let empty = interner.intern("");
+
+        assert!(Rc::ptr_eq(&a, &b));
+        assert!(!Rc::ptr_eq(&a, &c));
+        assert!(Rc::ptr_eq(&empty, &interner.intern("")));
+        assert_eq!(interner.len(), 3);
+        assert_eq!(Rc::strong_count(&a), 3);
+    }
+}
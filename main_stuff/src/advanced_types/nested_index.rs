@@ -0,0 +1,127 @@
+//The Hi alias makes the long type shorter to write, but every caller still has to know that it is
+// a map of maps of sets and walk through all three levels by hand. NestedIndex wraps the same
+// type and gives it an API in terms of what it stores: values filed under an outer key of numbers
+// and an inner key of strings.
+
+use std::collections::HashSet;
+
+use crate::advanced_types::Hi;
+
+#[derive(Debug, Clone, Default)]
+pub struct NestedIndex {
+    map: Hi,
+}
+
+impl NestedIndex {
+    pub fn new() -> NestedIndex {
+        NestedIndex::default()
+    }
+
+    //Returns false if the value was already filed under these keys.
+    pub fn insert(&mut self, outer: &[i32], inner: &[String], value: u32) -> bool {
+        self.map
+            .entry(outer.to_vec())
+            .or_default()
+            .entry(inner.to_vec())
+            .or_default()
+            .insert(value)
+    }
+
+    pub fn contains(&self, outer: &[i32], inner: &[String], value: u32) -> bool {
+        self.get(outer, inner).is_some_and(|values| values.contains(&value))
+    }
+
+    pub fn get(&self, outer: &[i32], inner: &[String]) -> Option<&HashSet<u32>> {
+        self.map.get(outer)?.get(inner)
+    }
+
+    //Removes every value under the inner key. An outer key that is left with nothing under it is
+    // removed too, so no empty maps are left behind.
+    pub fn remove_inner(&mut self, outer: &[i32], inner: &[String]) -> Option<HashSet<u32>> {
+        let inner_map = self.map.get_mut(outer)?;
+        let removed = inner_map.remove(inner);
+        if inner_map.is_empty() {
+            self.map.remove(outer);
+        }
+        removed
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    //Every (outer, inner, value) triple. The maps and sets have no order of their own, so the
+    // triples are sorted to make the order the same every time.
+    pub fn entries(&self) -> impl Iterator<Item = (&[i32], &[String], u32)> {
+        let mut entries: Vec<(&[i32], &[String], u32)> = self
+            .map
+            .iter()
+            .flat_map(|(outer, inner_map)| {
+                inner_map.iter().flat_map(move |(inner, values)| {
+                    values.iter().map(move |value| (outer.as_slice(), inner.as_slice(), *value))
+                })
+            })
+            .collect();
+        entries.sort();
+        entries.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(words: &[&str]) -> Vec<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn insert_reports_duplicates() {
+        let mut index = NestedIndex::new();
+
+        assert!(index.insert(&[1, 2], &strings(&["a"]), 7));
+        assert!(!index.insert(&[1, 2], &strings(&["a"]), 7));
+        assert!(index.insert(&[1, 2], &strings(&["a"]), 8));
+        assert!(index.contains(&[1, 2], &strings(&["a"]), 8));
+        assert!(!index.contains(&[1], &strings(&["a"]), 8));
+        assert_eq!(index.get(&[1, 2], &strings(&["a"])).map(HashSet::len), Some(2));
+    }
+
+    #[test]
+    fn remove_inner_cleans_up() {
+        let mut index = NestedIndex::new();
+        index.insert(&[1], &strings(&["a"]), 1);
+        index.insert(&[1], &strings(&["b"]), 2);
+
+        assert_eq!(index.remove_inner(&[1], &strings(&["a"])), Some(HashSet::from([1])));
+        assert_eq!(index.remove_inner(&[1], &strings(&["a"])), None);
+        assert!(!index.is_empty());
+
+        index.remove_inner(&[1], &strings(&["b"]));
+        assert!(index.is_empty());
+        assert_eq!(index.remove_inner(&[9], &strings(&["b"])), None);
+    }
+
+    #[test]
+    fn entries_are_flattened_and_sorted() {
+        let mut index = NestedIndex::new();
+        index.insert(&[2], &strings(&["x"]), 5);
+        index.insert(&[1, 3], &strings(&["y", "z"]), 9);
+        index.insert(&[1, 3], &strings(&["y", "z"]), 4);
+        index.insert(&[1, 3], &strings(&["a"]), 6);
+
+        let entries: Vec<(Vec<i32>, Vec<String>, u32)> = index
+            .entries()
+            .map(|(outer, inner, value)| (outer.to_vec(), inner.to_vec(), value))
+            .collect();
+        assert_eq!(
+            entries,
+            [
+                (vec![1, 3], strings(&["a"]), 6),
+                (vec![1, 3], strings(&["y", "z"]), 4),
+                (vec![1, 3], strings(&["y", "z"]), 9),
+                (vec![2], strings(&["x"]), 5),
+            ]
+        );
+    }
+}
@@ -0,0 +1,182 @@
+//More on the never type. An expression of type ! can be coerced into any other type, because it
+// never actually produces a value that would have to be that type. That is why the else branch
+// below can panic even though the if expression as a whole has to be an i32.
+
+use std::panic;
+use std::sync::mpsc::Receiver;
+use std::thread;
+
+pub fn choose(flag: bool) -> i32 {
+    if flag {
+        10
+    } else {
+        panic!("no value")
+    }
+}
+
+//A loop with no break never ends, so the loop itself has the type !. Parking the thread keeps it
+// from spinning the cpu while it waits forever.
+pub fn loop_forever() -> ! {
+    loop {
+        thread::park();
+    }
+}
+
+//A worker that handles requests for as long as the program runs, so it never returns either.
+// Both arms of a match have to be the same type. The Err arm is a `!`, which fits in with the ()
+// from the Ok arm. Once every sender is gone no more requests can arrive, so the thread is parked
+// for good. `continue` and `return` work the same way, which is what makes
+// `Err(_) => continue` possible inside of a loop.
+pub fn serve<T>(requests: Receiver<T>, mut handle: impl FnMut(T)) -> ! {
+    loop {
+        match requests.recv() {
+            Ok(request) => handle(request),
+            Err(_) => loop_forever(),
+        }
+    }
+}
+
+//Anything that never returns can be passed in to handle None, such as a closure that panics or
+// calls process::exit(). Since it never returns, the None arm still counts as producing a T.
+//This takes a fn pointer rather than `impl FnOnce() -> !`, because `!` can only be written as the
+// return type of a function or a fn pointer on stable Rust. A closure that captures nothing
+// still coerces to one.
+pub fn unwrap_or_diverge<T>(opt: Option<T>, diverge: fn() -> !) -> T {
+    match opt {
+        Some(value) => value,
+        None => diverge(),
+    }
+}
+
+//An error reporter that ends the program, in this case by panicking.
+pub fn report_error(message: &str) -> ! {
+    panic!("error: {}", message)
+}
+
+pub enum Input {
+    Number(i64),
+    Word(String),
+    Invalid(String),
+}
+
+//The Invalid arm has no String to give back, but report_error() returning ! still makes it fit.
+pub fn describe(input: Input) -> String {
+    match input {
+        Input::Number(number) => format!("the number {}", number),
+        Input::Word(word) => format!("the word {:?}", word),
+        Input::Invalid(reason) => report_error(&reason),
+    }
+}
+
+//An enum with no variants is uninhabited, there is no way to make a Void. A function taking one
+// can never be called, so it can promise to return any T at all. The empty match is allowed
+// because there are no variants left to cover.
+pub enum Void {}
+
+pub fn absurd<T>(void: Void) -> T {
+    match void {}
+}
+
+//A Result that can never be Err, for something like a trait that requires a Result but whose
+// implementation cannot fail. absurd() is what lets the Err arm type check.
+pub fn into_ok<T>(result: Result<T, Void>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(void) => absurd(void),
+    }
+}
+
+//Tries each attempt in turn and returns the first one that parses, along with how many attempts
+// it took. `continue` is a `!` too, so it fits in the Err arm where a u32 is expected.
+pub fn first_number(attempts: &[&str]) -> Option<(u32, usize)> {
+    for (tried, attempt) in attempts.iter().enumerate() {
+        let number: u32 = match attempt.trim().parse() {
+            Ok(number) => number,
+            Err(_) => continue,
+        };
+        return Some((number, tried + 1));
+    }
+    None
+}
+
+//choose() without the panic escaping. catch_unwind() turns the panic back into a value, so the
+// `!` branch ends up as the default. The panic message still gets printed by the panic hook.
+pub fn choose_or_default(flag: bool, default: i32) -> i32 {
+    panic::catch_unwind(|| choose(flag)).unwrap_or(default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn choose_true_has_a_value() {
+        assert_eq!(choose(true), 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "no value")]
+    fn choose_false_panics() {
+        choose(false);
+    }
+
+    #[test]
+    fn choose_or_default_for_both_branches() {
+        assert_eq!(choose_or_default(true, -1), 10);
+        assert_eq!(choose_or_default(false, -1), -1);
+    }
+
+    #[test]
+    fn unwrap_or_diverge_returns_the_value() {
+        assert_eq!(unwrap_or_diverge(Some(3), || panic!("unreachable")), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "nothing here")]
+    fn unwrap_or_diverge_diverges_on_none() {
+        unwrap_or_diverge(None::<u8>, || panic!("nothing here"));
+    }
+
+    #[test]
+    fn describe_the_valid_inputs() {
+        assert_eq!(describe(Input::Number(-4)), "the number -4");
+        assert_eq!(describe(Input::Word(String::from("hi"))), r#"the word "hi""#);
+    }
+
+    #[test]
+    #[should_panic(expected = "error: not a number or a word")]
+    fn describe_reports_invalid_input() {
+        describe(Input::Invalid(String::from("not a number or a word")));
+    }
+
+    #[test]
+    fn infallible_result_unwraps() {
+        let result: Result<&str, Void> = Ok("always");
+
+        assert_eq!(into_ok(result), "always");
+    }
+
+    #[test]
+    fn first_number_skips_failed_attempts() {
+        assert_eq!(first_number(&["x", "", " 7 ", "8"]), Some((7, 3)));
+        assert_eq!(first_number(&["5"]), Some((5, 1)));
+        assert_eq!(first_number(&["a", "b"]), None);
+    }
+
+    //serve() never returns, so it runs on a thread of its own. That thread is left parked once
+    // the test drops its sender.
+    #[test]
+    fn serve_handles_each_request() {
+        use std::sync::mpsc;
+
+        let (requests, receiver) = mpsc::channel();
+        let (replies, handled) = mpsc::channel();
+        thread::spawn(move || serve(receiver, move |word: &str| replies.send(word.len()).unwrap()));
+
+        for word in ["a", "abc", ""] {
+            requests.send(word).unwrap();
+        }
+
+        assert_eq!(handled.iter().take(3).collect::<Vec<_>>(), [1, 3, 0]);
+    }
+}
@@ -0,0 +1,240 @@
+//The newtype pattern hiding a HashMap. Callers of PhoneBook only see contacts and numbers, not
+// keys and values, and the map could be swapped out for something else without any of them
+// changing. PhoneNumber is a newtype as well, the only way to make one is through new(), so every
+// PhoneNumber that exists has already been checked.
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PhoneNumber(String);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PhoneNumberError {
+    //Something other than 0-9 showed up, at this char position.
+    NotADigit { found: char, position: usize },
+    TooShort { len: usize },
+    TooLong { len: usize },
+}
+
+impl Display for PhoneNumberError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PhoneNumberError::NotADigit { found, position } => {
+                write!(f, "'{}' at position {} is not a digit", found, position)
+            }
+            PhoneNumberError::TooShort { len } => write!(
+                f,
+                "{} digits is too short, a number needs at least {}",
+                len,
+                PhoneNumber::MIN_LEN
+            ),
+            PhoneNumberError::TooLong { len } => write!(
+                f,
+                "{} digits is too long, a number can have at most {}",
+                len,
+                PhoneNumber::MAX_LEN
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PhoneNumberError {}
+
+impl PhoneNumber {
+    pub const MIN_LEN: usize = 7;
+    pub const MAX_LEN: usize = 15;
+
+    pub fn new(number: &str) -> Result<PhoneNumber, PhoneNumberError> {
+        let not_a_digit = number.chars().enumerate().find(|(_, c)| !c.is_ascii_digit());
+        if let Some((position, found)) = not_a_digit {
+            return Err(PhoneNumberError::NotADigit { found, position });
+        }
+
+        //Only ASCII digits are left, so the length in bytes is the number of digits.
+        let len = number.len();
+        if len < Self::MIN_LEN {
+            return Err(PhoneNumberError::TooShort { len });
+        }
+        if len > Self::MAX_LEN {
+            return Err(PhoneNumberError::TooLong { len });
+        }
+
+        Ok(PhoneNumber(number.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for PhoneNumber {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+//Borrow<str> is what lets a HashMap<PhoneNumber, _> be searched with a plain &str, so there is no
+// need to build (and allocate) a PhoneNumber just to look one up. The map hashes the &str and
+// compares it against the keys, so Borrow comes with a promise that AsRef does not: a
+// PhoneNumber has to hash and compare exactly the same as its str. The derived Hash and Eq keep
+// that promise because the String is the only field, and String hashes the same as str. Adding
+// something like a country code field to the derive would quietly break every lookup.
+impl Borrow<str> for PhoneNumber {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for PhoneNumber {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PhoneBook {
+    entries: HashMap<String, PhoneNumber>,
+}
+
+impl PhoneBook {
+    pub fn new() -> PhoneBook {
+        PhoneBook::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    //Adding a name that is already there replaces the number and returns the old one.
+    pub fn add_contact(&mut self, name: &str, number: PhoneNumber) -> Option<PhoneNumber> {
+        self.entries.insert(name.to_string(), number)
+    }
+
+    pub fn lookup(&self, name: &str) -> Option<&PhoneNumber> {
+        self.entries.get(name)
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<PhoneNumber> {
+        self.entries.remove(name)
+    }
+
+    //A HashMap has no order, so the contacts are sorted by name before they are handed out.
+    pub fn contacts(&self) -> impl Iterator<Item = (&str, &PhoneNumber)> {
+        let mut contacts: Vec<(&str, &PhoneNumber)> =
+            self.entries.iter().map(|(name, number)| (name.as_str(), number)).collect();
+        contacts.sort_by_key(|(name, _)| *name);
+        contacts.into_iter()
+    }
+
+    //Adds every contact from `other`. When both books have the same name with different numbers,
+    // the number already in this book is kept, and the conflicting names are returned (sorted) so
+    // the caller can decide what to do with them.
+    pub fn merge(&mut self, other: PhoneBook) -> Vec<String> {
+        let mut conflicts = Vec::new();
+        for (name, number) in other.entries {
+            match self.entries.get(&name) {
+                Some(existing) if *existing != number => conflicts.push(name),
+                Some(_) => {}
+                None => {
+                    self.entries.insert(name, number);
+                }
+            }
+        }
+        conflicts.sort();
+        conflicts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn number(digits: &str) -> PhoneNumber {
+        PhoneNumber::new(digits).unwrap()
+    }
+
+    #[test]
+    fn phone_number_validation() {
+        assert_eq!(number("5551234").as_str(), "5551234");
+        assert_eq!(
+            PhoneNumber::new("555-1234"),
+            Err(PhoneNumberError::NotADigit { found: '-', position: 3 })
+        );
+        assert_eq!(PhoneNumber::new("123456"), Err(PhoneNumberError::TooShort { len: 6 }));
+        assert_eq!(PhoneNumber::new(&"9".repeat(16)), Err(PhoneNumberError::TooLong { len: 16 }));
+        assert_eq!(PhoneNumber::new(""), Err(PhoneNumberError::TooShort { len: 0 }));
+        assert_eq!(
+            PhoneNumber::new("12345").unwrap_err().to_string(),
+            "5 digits is too short, a number needs at least 7"
+        );
+    }
+
+    #[test]
+    fn lookup_hits_and_misses() {
+        let mut book = PhoneBook::new();
+        assert_eq!(book.add_contact("Ann", number("5550001")), None);
+        assert_eq!(book.add_contact("Ann", number("5550002")), Some(number("5550001")));
+
+        assert_eq!(book.lookup("Ann"), Some(&number("5550002")));
+        assert_eq!(book.lookup("Bob"), None);
+        assert_eq!(book.remove("Ann"), Some(number("5550002")));
+        assert!(book.is_empty());
+    }
+
+    #[test]
+    fn contacts_are_sorted_by_name() {
+        let mut book = PhoneBook::new();
+        book.add_contact("Carol", number("5550003"));
+        book.add_contact("Ann", number("5550001"));
+        book.add_contact("Bob", number("5550002"));
+
+        let names: Vec<&str> = book.contacts().map(|(name, _)| name).collect();
+        assert_eq!(names, ["Ann", "Bob", "Carol"]);
+    }
+
+    #[test]
+    fn merge_keeps_existing_numbers_on_conflict() {
+        let mut book = PhoneBook::new();
+        book.add_contact("Ann", number("5550001"));
+        book.add_contact("Bob", number("5550002"));
+
+        let mut other = PhoneBook::new();
+        other.add_contact("Ann", number("5559999"));
+        other.add_contact("Bob", number("5550002"));
+        other.add_contact("Dan", number("5550004"));
+
+        assert_eq!(book.merge(other), ["Ann"]);
+        assert_eq!(book.len(), 3);
+        assert_eq!(book.lookup("Ann"), Some(&number("5550001")));
+        assert_eq!(book.lookup("Dan"), Some(&number("5550004")));
+    }
+
+    #[test]
+    fn numbers_are_found_by_str() {
+        let mut owners: HashMap<PhoneNumber, String> = HashMap::new();
+        owners.insert(number("5551234"), String::from("Ferris"));
+        owners.insert(number("5559876"), String::from("Corro"));
+
+        assert_eq!(owners.get("5551234").map(String::as_str), Some("Ferris"));
+        assert!(owners.contains_key("5559876"));
+        assert_eq!(owners.get("0000000"), None);
+    }
+
+    #[test]
+    fn borrowed_form_hashes_the_same() {
+        use std::hash::{BuildHasher, RandomState};
+
+        let state = RandomState::new();
+        let phone = number("5551234");
+
+        assert_eq!(state.hash_one(&phone), state.hash_one("5551234"));
+        assert_eq!(<PhoneNumber as Borrow<str>>::borrow(&phone), phone.as_ref());
+    }
+}
@@ -0,0 +1,132 @@
+//The blog post workflow from chapter 17, with the state kept in the type instead of in a field.
+// Post<Draft> and Post<Published> are different types, so calling content() on a draft is not a
+// runtime error or an empty string, it simply does not compile. The states are unit structs with
+// no data, so a Post<S> takes up exactly as much room as its String and the state costs nothing.
+//PostState is sealed, it has a supertrait that lives in a private module. Code outside of this
+// module can name PostState but cannot implement Sealed, so nobody else can add a fourth state
+// that the methods below do not know about.
+
+use std::marker::PhantomData;
+
+mod private {
+    pub trait Sealed {}
+}
+
+pub trait PostState: private::Sealed {}
+
+pub struct Draft;
+
+pub struct PendingReview;
+
+pub struct Published;
+
+impl private::Sealed for Draft {}
+impl private::Sealed for PendingReview {}
+impl private::Sealed for Published {}
+
+impl PostState for Draft {}
+impl PostState for PendingReview {}
+impl PostState for Published {}
+
+//PhantomData<S> tells the compiler that Post uses S without actually storing one.
+pub struct Post<S: PostState> {
+    content: String,
+    state: PhantomData<S>,
+}
+
+pub type DraftPost = Post<Draft>;
+
+impl<S: PostState> Post<S> {
+    //Each transition moves the content into a Post of the next state. Taking self by value means
+    // the old one is gone afterwards, so there is never a draft and a published copy of one post.
+    fn into_state<N: PostState>(self) -> Post<N> {
+        Post {
+            content: self.content,
+            state: PhantomData,
+        }
+    }
+}
+
+impl Post<Draft> {
+    pub fn new() -> Post<Draft> {
+        Post {
+            content: String::new(),
+            state: PhantomData,
+        }
+    }
+
+    pub fn add_text(&mut self, text: &str) {
+        self.content.push_str(text);
+    }
+
+    pub fn request_review(self) -> Post<PendingReview> {
+        self.into_state()
+    }
+}
+
+impl Default for Post<Draft> {
+    fn default() -> Post<Draft> {
+        Post::new()
+    }
+}
+
+impl Post<PendingReview> {
+    pub fn approve(self) -> Post<Published> {
+        self.into_state()
+    }
+
+    //Goes back to being a draft, keeping the text so it can be fixed up.
+    pub fn reject(self) -> Post<Draft> {
+        self.into_state()
+    }
+}
+
+impl Post<Published> {
+    /// Only a published post has content to show. A draft does not have this method at all.
+    ///
+    /// ```compile_fail
+    /// use rust_book_chapter_19::advanced_types::post::DraftPost;
+    ///
+    /// let mut post = DraftPost::new();
+    /// post.add_text("not ready yet");
+    /// post.content();
+    /// ```
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem;
+
+    #[test]
+    fn draft_to_published() {
+        let mut post = DraftPost::new();
+        post.add_text("I ate a salad for lunch today");
+
+        let post = post.request_review().approve();
+
+        assert_eq!(post.content(), "I ate a salad for lunch today");
+    }
+
+    #[test]
+    fn reject_goes_back_to_draft() {
+        let mut post = DraftPost::new();
+        post.add_text("first");
+
+        let mut post: Post<Draft> = post.request_review().reject();
+        post.add_text(", second");
+        let post = post.request_review().approve();
+
+        assert_eq!(post.content(), "first, second");
+    }
+
+    #[test]
+    fn states_take_no_space() {
+        assert_eq!(mem::size_of::<Draft>(), 0);
+        assert_eq!(mem::size_of::<Post<Draft>>(), mem::size_of::<String>());
+        assert_eq!(mem::size_of::<Post<Published>>(), mem::size_of::<String>());
+    }
+}
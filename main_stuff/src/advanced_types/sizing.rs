@@ -0,0 +1,74 @@
+//Every Sized type has a size and an alignment that the compiler knows at compile time, so they can
+// be worked out inside of `const fn`s and stored in constants. Note that &str and &[T] are twice the
+// size of &T, because they are fat pointers holding a length next to the address.
+
+use std::mem;
+
+pub const fn size_of<T>() -> usize {
+    mem::size_of::<T>()
+}
+
+pub const fn align_of<T>() -> usize {
+    mem::align_of::<T>()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizeFacts {
+    pub name: &'static str,
+    pub size: usize,
+    pub align: usize,
+}
+
+impl SizeFacts {
+    pub const fn of<T>(name: &'static str) -> SizeFacts {
+        SizeFacts {
+            name,
+            size: size_of::<T>(),
+            align: align_of::<T>(),
+        }
+    }
+}
+
+//All of these are computed by the compiler, nothing here runs when the program does.
+pub const COMMON: [SizeFacts; 9] = [
+    SizeFacts::of::<u8>("u8"),
+    SizeFacts::of::<i32>("i32"),
+    SizeFacts::of::<u64>("u64"),
+    SizeFacts::of::<char>("char"),
+    SizeFacts::of::<&i32>("&i32"),
+    SizeFacts::of::<&str>("&str"),
+    SizeFacts::of::<&[u8]>("&[u8]"),
+    SizeFacts::of::<Box<dyn Fn()>>("Box<dyn Fn()>"),
+    SizeFacts::of::<Option<&i32>>("Option<&i32>"),
+];
+
+//(name, size, align) for each of the common types.
+pub fn facts() -> Vec<(&'static str, usize, usize)> {
+    COMMON.iter().map(|f| (f.name, f.size, f.align)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(name: &str) -> (&'static str, usize, usize) {
+        facts().into_iter().find(|f| f.0 == name).unwrap()
+    }
+
+    #[test]
+    fn facts_for_common_types() {
+        let pointer = size_of::<usize>();
+
+        assert_eq!(find("i32"), ("i32", 4, 4));
+        assert_eq!(find("u64"), ("u64", 8, align_of::<u64>()));
+        assert_eq!(find("&str"), ("&str", pointer * 2, pointer));
+        assert_eq!(find("Option<&i32>").1, pointer);
+    }
+
+    #[test]
+    fn usable_in_constants() {
+        const WORDS: usize = size_of::<[u64; 4]>() / size_of::<u64>();
+
+        assert_eq!(WORDS, 4);
+    }
+}
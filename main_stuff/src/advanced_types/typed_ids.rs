@@ -0,0 +1,161 @@
+//Ids are usually plain numbers, which makes it easy to pass a product id where a user id was
+// meant. Giving each kind of id its own newtype turns that mistake into a compile error. A
+// Store<UserId, T> only takes UserIds, and an IdGenerator<ProductId> only hands out ProductIds,
+// even though every one of them is a u64 underneath.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+//What every id type can do. from_raw() is what lets IdGenerator make ids of any type.
+pub trait Id: Copy + Eq + Hash + Display {
+    fn from_raw(raw: u64) -> Self;
+
+    fn raw(self) -> u64;
+}
+
+macro_rules! id_type {
+    ( $name:ident, $prefix:literal ) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(u64);
+
+        impl Id for $name {
+            fn from_raw(raw: u64) -> $name {
+                $name(raw)
+            }
+
+            fn raw(self) -> u64 {
+                self.0
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                write!(f, "{}-{}", $prefix, self.0)
+            }
+        }
+    };
+}
+
+id_type!(UserId, "user");
+id_type!(ProductId, "product");
+
+//Hands out 1, 2, 3 and so on as ids of type I. I is not stored anywhere, so PhantomData marks
+// the generator as belonging to it. Each generator counts on its own, so users and products
+// both start at 1.
+pub struct IdGenerator<I: Id> {
+    next: u64,
+    marker: PhantomData<I>,
+}
+
+impl<I: Id> IdGenerator<I> {
+    pub fn new() -> IdGenerator<I> {
+        IdGenerator {
+            next: 1,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn next_id(&mut self) -> I {
+        let id = I::from_raw(self.next);
+        self.next += 1;
+        id
+    }
+}
+
+impl<I: Id> Default for IdGenerator<I> {
+    fn default() -> IdGenerator<I> {
+        IdGenerator::new()
+    }
+}
+
+/// Values kept by their id. The id type is part of the store's type, so looking something up
+/// with the wrong kind of id does not compile.
+///
+/// ```compile_fail
+/// use rust_book_chapter_19::advanced_types::typed_ids::{IdGenerator, ProductId, Store, UserId};
+///
+/// let mut users: Store<UserId, String> = Store::new();
+/// let product: ProductId = IdGenerator::new().next_id();
+/// users.get(product);
+/// ```
+pub struct Store<I: Id, T> {
+    items: HashMap<I, T>,
+}
+
+impl<I: Id, T> Store<I, T> {
+    pub fn new() -> Store<I, T> {
+        Store {
+            items: HashMap::new(),
+        }
+    }
+
+    //Returns the value that was replaced, if there was one.
+    pub fn insert(&mut self, id: I, value: T) -> Option<T> {
+        self.items.insert(id, value)
+    }
+
+    pub fn get(&self, id: I) -> Option<&T> {
+        self.items.get(&id)
+    }
+
+    pub fn remove(&mut self, id: I) -> Option<T> {
+        self.items.remove(&id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl<I: Id, T> Default for Store<I, T> {
+    fn default() -> Store<I, T> {
+        Store::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_and_fetch_by_id() {
+        let mut ids = IdGenerator::new();
+        let mut users: Store<UserId, &str> = Store::new();
+
+        let ferris = ids.next_id();
+        let corro = ids.next_id();
+        users.insert(ferris, "Ferris");
+        users.insert(corro, "Corro");
+
+        assert_eq!(users.get(ferris), Some(&"Ferris"));
+        assert_eq!(users.remove(corro), Some("Corro"));
+        assert_eq!(users.get(corro), None);
+        assert_eq!(users.len(), 1);
+    }
+
+    #[test]
+    fn ids_display_with_a_prefix() {
+        assert_eq!(UserId::from_raw(42).to_string(), "user-42");
+        assert_eq!(ProductId::from_raw(7).to_string(), "product-7");
+        assert!(UserId::from_raw(1) < UserId::from_raw(2));
+    }
+
+    #[test]
+    fn generators_count_separately() {
+        let mut users: IdGenerator<UserId> = IdGenerator::new();
+        let mut products: IdGenerator<ProductId> = IdGenerator::default();
+
+        assert_eq!(users.next_id().raw(), 1);
+        assert_eq!(users.next_id().raw(), 2);
+        assert_eq!(products.next_id().raw(), 1);
+        assert_eq!(users.next_id(), UserId::from_raw(3));
+        assert_eq!(products.next_id(), ProductId::from_raw(2));
+    }
+}
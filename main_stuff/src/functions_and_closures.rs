@@ -0,0 +1,112 @@
+use crate::report::SectionReport;
+
+//Times calling a closure against calling a function pointer, see bench::compare.
+pub mod bench;
+//Function pointers and closures stored by name, see fn_registry::FnRegistry and
+// fn_registry::ClosureRegistry.
+pub mod fn_registry;
+//A guard that runs a closure when it goes out of scope, see scope_exit::ScopeExit.
+pub mod scope_exit;
+
+//Function pointers can be passed to a function instead of closures as well.
+pub fn closure_add<F>(f: &F) -> u32 where F: Fn(u32) -> u32 {
+    f(2)
+}
+
+//In general this is not a good way to write the function. This is because the closure syntax
+// above can accept function pointers. However, the function pointer syntax cannot accept
+// closures. This is because the fn type implements Fn, FnMut and FnOnce.
+pub fn function_ptr_add(f: fn(u32) -> u32) -> u32 {
+    f(3)
+}
+
+//Both lessons above in one function. A generic F: Fn works for a fn item like foo, a closure
+// that captures nothing and a closure that does. So a parameter should be a generic Fn (or
+// `impl Fn`) rather than a `fn` pointer unless it really has to be a pointer, such as when
+// passing it to C. Each F also gets its own copy of the function, so the call can be inlined,
+// where a fn pointer is a call to whatever address it holds.
+//This would be called run() as well, but that name is already taken by the section's run().
+pub fn call_with_fn_or_closure<F: Fn(u32) -> u32>(f: F, input: u32) -> u32 {
+    f(input)
+}
+
+pub fn foo(i: u32) -> u32 {
+    i + 1
+}
+
+//Closures can also be returned. However, they must be wrapped in a smart pointer. Otherwise,
+// the compiler will not know what the correct size for them is. The below code is directly
+// from the Rust book.
+pub fn returns_closure() -> Box<dyn Fn(i32) -> i32> {
+    Box::new(|x| x + 1)
+}
+
+pub fn run() -> SectionReport {
+    let mut report = SectionReport::new("closures");
+
+    let capture = 1;
+
+    //If the closure does not capture anything, it can be coerced into a function pointer by the
+    // compiler. In order to demonstrate the difference between closures and function pointers
+    // the closure must capture something from the environment.
+    let close = |i: u32| {
+        i + capture
+    };
+
+    report.push(format!("add from closure {}", closure_add(&close))); //Passed a closure.
+    report.push(format!("add from closure {}", closure_add(&foo))); //Passed a function pointer.
+    // function_ptr_add(close); //Passed a closure. Will not compile, read above for more info.
+    report.push(format!("add from function ptr {}", function_ptr_add(foo))); //Passed a function pointer.
+
+    let returned_value = returns_closure();
+
+    report.push(format!("returned value: {}", returned_value(2)));
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closure_add_accepts_closures_and_functions() {
+        let capture = 10;
+        let close = |i: u32| i + capture;
+
+        assert_eq!(closure_add(&close), 12);
+        assert_eq!(closure_add(&foo), 3);
+    }
+
+    #[test]
+    fn function_ptr_add_accepts_functions() {
+        assert_eq!(function_ptr_add(foo), 4);
+    }
+
+    #[test]
+    fn call_with_fn_or_closure_accepts_everything() {
+        let offset = 100;
+
+        assert_eq!(call_with_fn_or_closure(foo, 1), 2);
+        assert_eq!(call_with_fn_or_closure(|i| i + offset, 1), 101);
+        assert_eq!(call_with_fn_or_closure(|i| i * 2, 21), 42);
+
+        let pointer: fn(u32) -> u32 = foo;
+        assert_eq!(call_with_fn_or_closure(pointer, 4), 5);
+    }
+
+    #[test]
+    fn returned_closure_adds_one() {
+        assert_eq!(returns_closure()(2), 3);
+    }
+
+    #[test]
+    fn report_contains_key_lines() {
+        let report = run();
+
+        assert_eq!(report.name, "closures");
+        assert_eq!(report.count("add from closure 3"), 2);
+        assert_eq!(report.count("add from function ptr 4"), 1);
+        assert_eq!(report.count("returned value: 3"), 1);
+    }
+}
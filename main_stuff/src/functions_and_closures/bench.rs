@@ -0,0 +1,63 @@
+//A rough way to see what calling a closure costs next to calling a function pointer. A closure
+// passed as a generic F is its own type, so the compiler knows exactly which code runs and can
+// inline it. A fn pointer is only an address, so unless the compiler can work out which function
+// it points to, every call has to jump through it.
+
+//The numbers this gives are only a rough idea. Nothing here warms up the cpu or repeats runs the
+// way a real benchmark library such as criterion would, so it is a teaching tool and not a
+// measurement to trust.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+fn add_one(i: u64) -> u64 {
+    i.wrapping_add(1)
+}
+
+//Calls f once per iteration, feeding each result into the next call so the calls cannot be
+// skipped. black_box() keeps the compiler from working the whole loop out ahead of time.
+fn time_calls<F>(iterations: usize, f: F) -> Duration where F: Fn(u64) -> u64 {
+    let start = Instant::now();
+    let mut value = 0;
+    for _ in 0..iterations {
+        value = f(black_box(value));
+    }
+    black_box(value);
+    start.elapsed()
+}
+
+//Returns how long the closure took and then how long the fn pointer took.
+pub fn compare(iterations: usize) -> (Duration, Duration) {
+    let step = black_box(1);
+    let closure = |i: u64| i.wrapping_add(step);
+
+    //Going through black_box hides which function the pointer points to, so the calls really do
+    // go through the pointer.
+    let pointer: fn(u64) -> u64 = black_box(add_one);
+
+    let closure_time = time_calls(iterations, closure);
+    let pointer_time = time_calls(iterations, pointer);
+
+    (closure_time, pointer_time)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compare_times_both_kinds_of_call() {
+        let (closure_time, pointer_time) = compare(1_000);
+
+        assert!(closure_time >= Duration::ZERO);
+        assert!(pointer_time >= Duration::ZERO);
+    }
+
+    #[test]
+    fn compare_handles_zero_iterations() {
+        let (closure_time, pointer_time) = compare(0);
+
+        assert!(closure_time < Duration::from_secs(1));
+        assert!(pointer_time < Duration::from_secs(1));
+    }
+}
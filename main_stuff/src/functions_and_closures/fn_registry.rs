@@ -0,0 +1,96 @@
+//Because a function pointer is just a type like any other, `fn` pointers can be stored inside of a
+// collection and looked up later. Every value in the map has the same type, `fn(u32) -> u32`,
+// even though each one points at a different function.
+//Note that only functions (and closures that capture nothing) can be registered here. A closure
+// that captures something from its environment is not a `fn` and will not compile, that is what
+// ClosureRegistry below is for.
+
+use std::collections::HashMap;
+
+#[derive(Default)]
+pub struct FnRegistry {
+    functions: HashMap<String, fn(u32) -> u32>,
+}
+
+impl FnRegistry {
+    pub fn new() -> FnRegistry {
+        FnRegistry::default()
+    }
+
+    //Registering a name a second time replaces the function that was there.
+    pub fn register(&mut self, name: &str, f: fn(u32) -> u32) {
+        self.functions.insert(name.to_string(), f);
+    }
+
+    //Returns None if nothing was registered under the name.
+    pub fn call(&self, name: &str, arg: u32) -> Option<u32> {
+        self.functions.get(name).map(|f| f(arg))
+    }
+}
+
+//The same idea except that the values are trait objects instead of function pointers. Every
+// closure has its own unique type, so they have to be boxed to fit into the same map, but in
+// return closures that capture their environment can be stored as well.
+#[derive(Default)]
+pub struct ClosureRegistry {
+    closures: HashMap<String, Box<dyn Fn(u32) -> u32>>,
+}
+
+impl ClosureRegistry {
+    pub fn new() -> ClosureRegistry {
+        ClosureRegistry::default()
+    }
+
+    //The closure must be 'static because the registry keeps it around, so anything it captures
+    // has to be moved into it.
+    pub fn register(&mut self, name: &str, f: impl Fn(u32) -> u32 + 'static) {
+        self.closures.insert(name.to_string(), Box::new(f));
+    }
+
+    pub fn call(&self, name: &str, arg: u32) -> Option<u32> {
+        self.closures.get(name).map(|f| f(arg))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions_and_closures::foo;
+
+    #[test]
+    fn registered_function_is_called() {
+        let mut registry = FnRegistry::new();
+        registry.register("foo", foo);
+        registry.register("double", |i| i * 2);
+
+        assert_eq!(registry.call("foo", 1), Some(2));
+        assert_eq!(registry.call("double", 21), Some(42));
+    }
+
+    #[test]
+    fn registering_again_replaces() {
+        let mut registry = FnRegistry::new();
+        registry.register("f", foo);
+        registry.register("f", |i| i * 10);
+
+        assert_eq!(registry.call("f", 3), Some(30));
+    }
+
+    #[test]
+    fn missing_name_is_none() {
+        let registry = FnRegistry::new();
+        assert_eq!(registry.call("foo", 1), None);
+    }
+
+    #[test]
+    fn closure_registry_holds_capturing_closures() {
+        let multiplier = 7;
+        let mut registry = ClosureRegistry::new();
+        registry.register("times", move |i| i * multiplier);
+        registry.register("foo", foo);
+
+        assert_eq!(registry.call("times", 6), Some(42));
+        assert_eq!(registry.call("foo", 6), Some(7));
+        assert_eq!(registry.call("missing", 6), None);
+    }
+}
@@ -0,0 +1,90 @@
+//A closure can be stored and run later, and Drop runs at the end of a scope no matter how the
+// scope is left. Putting the two together gives a guard that runs some cleanup when it goes out
+// of scope, even on an early return or a panic. This is handy around unsafe code where something
+// has to be undone by hand, such as freeing memory that was allocated manually.
+
+//The closure is kept in an Option because Drop only gets `&mut self`, and calling an FnOnce needs
+// to take it by value. Option::take() moves it out and leaves None behind.
+pub struct ScopeExit<F: FnOnce()> {
+    f: Option<F>,
+}
+
+pub fn defer<F: FnOnce()>(f: F) -> ScopeExit<F> {
+    ScopeExit::defer(f)
+}
+
+impl<F: FnOnce()> ScopeExit<F> {
+    pub fn defer(f: F) -> ScopeExit<F> {
+        ScopeExit { f: Some(f) }
+    }
+
+    //Throws the closure away without running it, for when the cleanup turns out not to be needed.
+    pub fn cancel(mut self) {
+        self.f = None;
+    }
+}
+
+impl<F: FnOnce()> Drop for ScopeExit<F> {
+    fn drop(&mut self) {
+        if let Some(f) = self.f.take() {
+            f();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::panic;
+
+    #[test]
+    fn runs_at_the_end_of_the_scope() {
+        let ran = Cell::new(false);
+        {
+            let _guard = defer(|| ran.set(true));
+            assert!(!ran.get());
+        }
+        assert!(ran.get());
+    }
+
+    #[test]
+    fn runs_in_reverse_order() {
+        let order = Cell::new(0);
+        let first = Cell::new(0);
+        let second = Cell::new(0);
+        {
+            let _a = ScopeExit::defer(|| {
+                order.set(order.get() + 1);
+                first.set(order.get());
+            });
+            let _b = ScopeExit::defer(|| {
+                order.set(order.get() + 1);
+                second.set(order.get());
+            });
+        }
+        assert_eq!((first.get(), second.get()), (2, 1));
+    }
+
+    #[test]
+    fn cancel_skips_the_closure() {
+        let ran = Cell::new(false);
+        {
+            let guard = defer(|| ran.set(true));
+            guard.cancel();
+        }
+        assert!(!ran.get());
+    }
+
+    #[test]
+    fn runs_when_unwinding() {
+        let ran = Cell::new(false);
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            let _guard = defer(|| ran.set(true));
+            panic!("early exit");
+        }));
+
+        assert!(result.is_err());
+        assert!(ran.get());
+    }
+}
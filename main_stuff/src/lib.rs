@@ -0,0 +1,11 @@
+//Each module below covers one section of chapter 19 of the Rust book. The items that used to be
+// nested inside of the demo functions live at module scope so that they can be reused and
+// tested. Each module also exposes a `run()` function which builds a report of the demo for that
+// section.
+pub mod advanced_traits;
+pub mod advanced_types;
+pub mod functions_and_closures;
+pub mod macros_demo;
+pub mod report;
+pub mod sections;
+pub mod unsafe_rust;
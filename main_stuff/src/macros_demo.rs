@@ -0,0 +1,776 @@
+//vec_new! deliberately pushes one element at a time to show the repetition syntax, which clippy
+// would otherwise flag everywhere the macro is expanded in this module.
+#![allow(clippy::vec_init_then_push)]
+
+//There are two different types of macros.
+// 1) "declarative macros" which use `macro_rules!`
+// 2) "procedural macros" which are divided into three types
+//   - Custom [#derive] macros;
+//   - Attribute-like macros;
+//   - Function-like macros;
+
+//There are some benefits to macros over functions. Macros have a variable number of arguments.
+// Macros are also expanded before the compiler interprets the code. The trade offs and  that
+// macros are more complex than functions, and the macros must be defined before they are
+// called in a file.
+
+//The below macro is a `declarative macro`. It will replace the code with the code in the macro
+
+#[macro_export] //This annotation says that this should be brought into scope when the crate is loaded.
+macro_rules! vec_new { //This is the macro to make a vector followed by the name.
+
+    //Declarative macros are similar to match expressions, this is the single arm of this macro.
+    //The `$` is used to denote the Rust code matching pattern. `$x:expr` matches any Rust
+    // expression and gives the expression the name `$x`. `*` specifies that the pattern
+    // matches 0 or more of whatever precedes it.
+    //So essentially the 'match' here takes all of the expressions at the same time. Then inside
+    // the arm each expression is called individually and pushed into the vector using the
+    // `$()*` syntax.
+    ( $( $x:expr ),* ) => {
+        {
+            #[allow(unused_mut)] //With no expressions nothing is pushed, so the mut goes unused.
+            let mut temp_vec = Vec::new();
+            $(
+                temp_vec.push($x);
+            )*
+            temp_vec
+        }
+    };
+}
+
+//It should be noted that macros are much more complex that covered here. They seem to
+// essentially be their own programming language. The things that are worthwhile to keep in mind
+// are when a macro should be used (the capabilities of macros). Then I can look up the
+// specifics to build my individual macro. A good source is listed below for macros.
+// https://veykril.github.io/tlborm/
+
+//The same as vec_new! except that it also returns how many expressions were passed in. Macros
+// cannot count directly, but they can call themselves. __count_exprs! peels off one expression
+// at a time and adds 1 for each, so `__count_exprs!(a, b, c)` expands to `1 + 1 + 1 + 0`. That
+// is all worked out during expansion, none of the expressions are evaluated to count them.
+#[macro_export]
+macro_rules! vec_new_counted {
+    ( $( $x:expr ),* $(,)? ) => {
+        ($crate::vec_new!($( $x ),*), $crate::__count_exprs!($( $x ),*))
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __count_exprs {
+    () => { 0usize };
+    ( $head:expr $( , $tail:expr )* ) => { 1usize + $crate::__count_exprs!($( $tail ),*) };
+}
+
+//Prints each `label => value` pair on its own line as `label: value`. A trailing comma is allowed.
+// If a writer followed by a `;` comes first, the lines are written to it with writeln! instead and
+// the whole macro evaluates to an io::Result<()>.
+//The first arm must be the one without a writer. A writer such as `buf` parses as an expression
+// but is then followed by `;` instead of `=>`, so the first arm fails and the second one is tried.
+#[macro_export]
+macro_rules! print_each {
+    ( $( $label:expr => $value:expr ),* $(,)? ) => {
+        $(
+            println!("{}: {}", $label, $value);
+        )*
+    };
+    ( $writer:expr; $( $label:expr => $value:expr ),* $(,)? ) => {
+        (|| -> ::std::io::Result<()> {
+            use ::std::io::Write as _;
+            $(
+                writeln!($writer, "{}: {}", $label, $value)?;
+            )*
+            Ok(())
+        })()
+    };
+}
+
+//Returns true if the value matches any of the patterns, for example
+// `matches_any!(x, 1 | 2 | Foo::Bar)`. Each pattern is matched as a `pat_param` so that the `|`
+// between them is left for the macro to use as a separator. They are then or-ed back together
+// inside of a single matches!. Guards are not supported.
+#[macro_export]
+macro_rules! matches_any {
+    ( $value:expr, $( $pattern:pat_param )|+ $(,)? ) => {
+        matches!($value, $( $pattern )|+)
+    };
+}
+
+//Evaluates the expression `count` times, throwing away whatever it returns. The expression is
+// pasted into the body of the loop, so it really is evaluated again on each pass rather than
+// once with the result reused.
+#[macro_export]
+macro_rules! repeat_call {
+    ( $count:expr, $call:expr $(,)? ) => {
+        for _ in 0..$count {
+            $call;
+        }
+    };
+}
+
+//Runs a block (or any expression), prints how long it took and then evaluates to whatever the
+// block evaluated to. An optional string literal names the block in the printed line.
+//The `@timed` arm is an internal rule, it is the part that does the timing and gives back both
+// the value and the elapsed Duration. Starting internal rules with `@` is a common convention
+// because `@` cannot start an expression, so the rule can never be matched by accident.
+#[macro_export]
+macro_rules! profile_block {
+    ( @timed $body:expr ) => {
+        {
+            let start = ::std::time::Instant::now();
+            let value = $body;
+            (value, start.elapsed())
+        }
+    };
+    ( $label:literal, $body:expr ) => {
+        {
+            let (value, elapsed) = $crate::profile_block!(@timed $body);
+            println!("{} took {:?}", $label, elapsed);
+            value
+        }
+    };
+    ( $body:expr ) => {
+        $crate::profile_block!("block", $body)
+    };
+}
+
+//Unions are unsafe to read because nothing records which field was written, see
+// unsafe_rust::unions. An enum is the safe version of the same idea, since it stores a tag along
+// with the value. tagged_union! takes union-like syntax, `tagged_union!(Value { i: i32, f: f32 })`,
+// and turns it into an enum with one variant per field plus an accessor for each one:
+//
+//     enum Value { I(i32), F(f32) }
+//     impl Value { fn as_i(&self) -> Option<&i32>; fn as_f(&self) -> Option<&f32>; }
+//
+//macro_rules! cannot build new identifiers out of old ones, such as `as_i` and `I` out of `i`, so
+// it is a procedural macro instead.
+pub use procedural_macros::tagged_union;
+
+//Builds a lookup table that exists entirely at compile time.
+// `const_map!(pub mod codes { "a" => 1 })` expands to a module holding a static slice of the pairs
+// and a lookup() function that finds a key with a binary search. A binary search only works on
+// sorted keys, so the keys have to be written in sorted order. That is checked by a constant
+// assertion, so out of order keys are a compile error rather than a lookup that quietly misses.
+#[macro_export]
+macro_rules! const_map {
+    ( $vis:vis mod $name:ident { $( $key:literal => $value:expr ),* $(,)? } ) => {
+        $vis mod $name {
+            pub static ENTRIES: &[(&str, i32)] = &[ $( ($key, $value) ),* ];
+
+            const _: () = assert!(
+                $crate::macros_demo::__keys_sorted(&[ $( $key ),* ]),
+                "const_map! keys must be in sorted order with no duplicates"
+            );
+
+            #[allow(dead_code)]
+            pub fn lookup(key: &str) -> Option<i32> {
+                ENTRIES
+                    .binary_search_by(|(entry, _)| (*entry).cmp(key))
+                    .ok()
+                    .map(|index| ENTRIES[index].1)
+            }
+        }
+    };
+}
+
+//Used by const_map! to check its keys while compiling. Iterators and Ord::cmp() cannot be used in a
+// `const fn` yet, so the strings are compared byte by byte with while loops, which gives the same
+// order as str::cmp().
+#[doc(hidden)]
+pub const fn __keys_sorted(keys: &[&str]) -> bool {
+    let mut i = 1;
+    while i < keys.len() {
+        let (a, b) = (keys[i - 1].as_bytes(), keys[i].as_bytes());
+        let mut j = 0;
+        while j < a.len() && j < b.len() && a[j] == b[j] {
+            j += 1;
+        }
+        let less = if j < a.len() && j < b.len() { a[j] < b[j] } else { a.len() < b.len() };
+        if !less {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+//Writes out a whole newtype from one line, `newtype!(pub UserId(u32))`. That is the tuple struct,
+// a new() constructor, into_inner(), Deref to the wrapped type so its methods can still be
+// called, and a Display that forwards to the wrapped value. A format string can be added after a
+// comma to change the Display, `newtype!(Meters(f64), "{} m")`, where `{}` is the wrapped value.
+//Either way the wrapped type has to implement Display, since the impl is always generated.
+#[macro_export]
+macro_rules! newtype {
+    ( $( #[$meta:meta] )* $vis:vis $name:ident ( $inner:ty ) $(,)? ) => {
+        $crate::newtype!($( #[$meta] )* $vis $name($inner), "{}");
+    };
+    ( $( #[$meta:meta] )* $vis:vis $name:ident ( $inner:ty ), $format:literal $(,)? ) => {
+        $( #[$meta] )*
+        $vis struct $name($vis $inner);
+
+        #[allow(dead_code)]
+        impl $name {
+            pub fn new(value: $inner) -> $name {
+                $name(value)
+            }
+
+            pub fn into_inner(self) -> $inner {
+                self.0
+            }
+        }
+
+        impl ::std::ops::Deref for $name {
+            type Target = $inner;
+
+            fn deref(&self) -> &$inner {
+                &self.0
+            }
+        }
+
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                write!(f, $format, self.0)
+            }
+        }
+    };
+}
+
+//The two From impls nearly every newtype wants, `impl_from_for_newtype!(Wrapper(Vec<String>))`
+// gives both `Wrapper::from(vec)` and `Vec::<String>::from(wrapper)`. A generic newtype lists its
+// parameters first, `impl_from_for_newtype!(<T> Stack<T>(Vec<T>))`. The wrapped type cannot be a
+// bare parameter like `<T> Tagged<T>(T)` though, since `impl<T> From<Tagged<T>> for T` would
+// implement a trait from std for any T at all, which the orphan rule does not allow.
+#[macro_export]
+macro_rules! impl_from_for_newtype {
+    ( $name:ident ( $inner:ty ) ) => {
+        impl ::std::convert::From<$inner> for $name {
+            fn from(value: $inner) -> $name {
+                $name(value)
+            }
+        }
+
+        impl ::std::convert::From<$name> for $inner {
+            fn from(value: $name) -> $inner {
+                value.0
+            }
+        }
+    };
+    ( < $( $param:ident ),+ > $name:ident ( $inner:ty ) ) => {
+        impl< $( $param ),+ > ::std::convert::From<$inner> for $name< $( $param ),+ > {
+            fn from(value: $inner) -> $name< $( $param ),+ > {
+                $name(value)
+            }
+        }
+
+        impl< $( $param ),+ > ::std::convert::From<$name< $( $param ),+ >> for $inner {
+            fn from(value: $name< $( $param ),+ >) -> $inner {
+                value.0
+            }
+        }
+    };
+}
+
+//`procedural macros` act like functions. They accept input and produce output instead of
+// replacing the code. Procedural macros must be generated inside of a specific type of crate
+// lib.rs inside the `procedural_macros` crate is where this is done.
+
+//Procedural macros themselves must be declared inside their own crate. That means that if I
+// want to have a trait (such as HelloMacro below) I must declare the trait itself inside a
+// separate crate. This leads to three crates inside a workspace.
+use procedural_macros::HelloMacro;
+pub use procedural_trait::HelloMacro;
+
+use crate::report::SectionReport;
+
+//The macro can then be used just like a normal macro.
+#[derive(HelloMacro)]
+pub struct TheNamedStruct;
+
+pub fn vec_new_demo() -> Vec<i32> {
+    vec_new![1,2,3]
+}
+
+pub fn run() -> SectionReport {
+    let mut report = SectionReport::new("macros");
+
+    report.push(format!("vec_new: {:?}", vec_new_demo()));
+
+    //Because the macro is the same for each instance of a class, it works like a static function
+    // call. The writer version is used so the greeting ends up inside of the report.
+    let mut output = Vec::new();
+    TheNamedStruct::hello_macro_to(&mut output).expect("writing to a Vec cannot fail");
+    report.push_output(&output);
+
+    //The second type of macro is attribute-like macros. For example inside `#derive(HelloMacro)`
+    // `derive` is the attribute. attribute-like macros allow for defining custom attributes. These
+    // can be used on other things such as function as well and are not limited to just structs and
+    // enums.
+    //As for implementation of attribute-like macros, they have a similar implementation to the
+    // custom #derive macro (such as HelloMacro above). However, they have more capabilities.
+
+    //The third type of macro is function-like macros. These provide some benefits of declarative-
+    // macros and some benefits of procedural-macros. They can take an unknown number of arguments,
+    // however, they are much more powerful than declarative-macros.
+    //Implementation is again similar to custom #derive macros.
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vec_new_builds_vector_in_order() {
+        assert_eq!(vec_new_demo(), vec![1, 2, 3]);
+        assert_eq!(vec_new!["a", "b"], vec!["a", "b"]);
+    }
+
+    #[test]
+    fn vec_new_counted_counts_the_arguments() {
+        let (empty, count): (Vec<i32>, usize) = vec_new_counted!();
+        assert_eq!((empty.len(), count), (0, 0));
+
+        assert_eq!(vec_new_counted!(7), (vec![7], 1));
+        assert_eq!(vec_new_counted!(1, 2, 3,), (vec![1, 2, 3], 3));
+
+        let (words, count) = vec_new_counted!("a".to_string(), "b".repeat(2), String::new());
+        assert_eq!(count, words.len());
+        assert_eq!(count, 3);
+    }
+
+    #[test]
+    fn print_each_writes_one_line_per_pair() {
+        let mut output = Vec::new();
+        let x = 5;
+        print_each!(output; "x" => x, "doubled" => x * 2,).unwrap();
+        print_each!(output; "single" => "value").unwrap();
+
+        assert_eq!(String::from_utf8(output).unwrap(), "x: 5\ndoubled: 10\nsingle: value\n");
+    }
+
+    #[test]
+    fn matches_any_numbers() {
+        let x = 2;
+
+        assert!(matches_any!(x, 1 | 2 | 5));
+        assert!(matches_any!(x, 0..=2));
+    }
+
+    #[test]
+    fn matches_any_enum_variants() {
+        #[allow(dead_code)]
+        enum Foo {
+            Bar,
+            Baz(u32),
+            Qux,
+        }
+
+        assert!(matches_any!(Foo::Bar, Foo::Bar | Foo::Qux));
+        assert!(matches_any!(Foo::Baz(7), Foo::Qux | Foo::Baz(_)));
+    }
+
+    #[test]
+    fn matches_any_without_a_match_is_false() {
+        assert!(!matches_any!(10, 1 | 3 | 5));
+        assert!(!matches_any!(Some(4), None | Some(5)));
+    }
+
+    #[test]
+    fn repeat_call_runs_the_expression_each_time() {
+        use crate::unsafe_rust::counter::COUNTER;
+
+        //COUNTER is shared with the unsafe section, so this has to wait for anything running it.
+        let _guard =
+            crate::unsafe_rust::RUN_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        COUNTER.reset();
+        repeat_call!(5, COUNTER.increment());
+        assert_eq!(COUNTER.get(), 5);
+
+        let mut calls = Vec::new();
+        repeat_call!(3, calls.push(calls.len()));
+        assert_eq!(calls, [0, 1, 2]);
+        let never = calls.len() - 3;
+        repeat_call!(never, calls.clear());
+        assert_eq!(calls.len(), 3);
+    }
+
+    #[test]
+    fn profile_block_returns_the_inner_value() {
+        let named = profile_block!("sum", {
+            let values = [1, 2, 3];
+            values.iter().sum::<i32>()
+        });
+        let unnamed = profile_block!(String::from("unchanged"));
+
+        assert_eq!(named, 6);
+        assert_eq!(unnamed, "unchanged");
+    }
+
+    #[test]
+    fn profile_block_measures_elapsed_time() {
+        let pause = std::time::Duration::from_millis(5);
+        let (value, elapsed) = profile_block!(@timed {
+            std::thread::sleep(pause);
+            'x'
+        });
+
+        assert_eq!(value, 'x');
+        assert!(elapsed >= pause);
+    }
+
+    #[test]
+    fn tagged_union_accessors() {
+        tagged_union!(#[derive(Debug, PartialEq)] Value { i: i32, f: f32, text: String });
+
+        let int = Value::I(5);
+        let float = Value::F(2.5);
+        let text = Value::Text(String::from("hi"));
+
+        assert_eq!(int.as_i(), Some(&5));
+        assert_eq!(int.as_f(), None);
+        assert_eq!(float.as_f(), Some(&2.5));
+        assert_eq!(float.as_text(), None);
+        assert_eq!(text.as_text().map(String::as_str), Some("hi"));
+        assert_eq!(text.as_i(), None);
+    }
+
+    #[test]
+    fn tagged_union_with_one_field() {
+        tagged_union!(Only { value: u8 });
+
+        assert_eq!(Only::Value(3).as_value(), Some(&3));
+    }
+
+    const_map!(mod http_codes {
+        "created" => 201,
+        "not found" => 404,
+        "ok" => 200,
+        "teapot" => 418,
+    });
+
+    #[test]
+    fn const_map_lookup() {
+        assert_eq!(http_codes::lookup("ok"), Some(200));
+        assert_eq!(http_codes::lookup("teapot"), Some(418));
+        assert_eq!(http_codes::lookup("created"), Some(201));
+        assert_eq!(http_codes::lookup("gone"), None);
+        assert_eq!(http_codes::lookup(""), None);
+        assert_eq!(http_codes::ENTRIES.len(), 4);
+    }
+
+    #[test]
+    fn keys_sorted_check() {
+        assert!(__keys_sorted(&[]));
+        assert!(__keys_sorted(&["a", "ab", "b"]));
+        assert!(!__keys_sorted(&["b", "a"]));
+        assert!(!__keys_sorted(&["ab", "a"]));
+        assert!(!__keys_sorted(&["a", "a"]));
+    }
+
+    #[test]
+    fn newtype_over_u32() {
+        newtype!(#[derive(Debug, Clone, Copy, PartialEq)] UserId(u32));
+
+        let id = UserId::new(42);
+        assert_eq!(id.to_string(), "42");
+        assert_eq!(*id + 1, 43);
+        assert_eq!(id.into_inner(), 42);
+        assert_eq!(id, UserId(42));
+    }
+
+    #[test]
+    fn newtype_over_string_with_a_format() {
+        newtype!(pub Username(String), "@{}");
+
+        let name = Username::new(String::from("ferris"));
+        assert_eq!(name.to_string(), "@ferris");
+        assert_eq!(name.len(), 6);
+        assert!(name.starts_with("fer"));
+        assert_eq!(name.into_inner(), "ferris");
+    }
+
+    #[test]
+    fn impl_from_for_newtype_over_string() {
+        #[derive(Debug, PartialEq)]
+        struct Email(String);
+
+        impl_from_for_newtype!(Email(String));
+
+        let email = Email::from(String::from("a@b.c"));
+        assert_eq!(email, Email(String::from("a@b.c")));
+        let back: String = email.into();
+        assert_eq!(back, "a@b.c");
+
+        let email: Email = String::from("x@y.z").into();
+        assert_eq!(String::from(email), "x@y.z");
+    }
+
+    #[test]
+    fn impl_from_for_newtype_with_generics() {
+        #[derive(Debug, PartialEq)]
+        struct Keyed<K, V>(Vec<(K, V)>);
+
+        impl_from_for_newtype!(<K, V> Keyed(Vec<(K, V)>));
+
+        let keyed = Keyed::from(vec![("a", 1), ("b", 2)]);
+        assert_eq!(keyed.0.len(), 2);
+        assert_eq!(Vec::from(keyed), [("a", 1), ("b", 2)]);
+    }
+
+    #[test]
+    fn report_contains_key_lines() {
+        let report = run();
+
+        assert_eq!(report.name, "macros");
+        assert_eq!(report.lines, vec!["vec_new: [1, 2, 3]", "Hello, Macro! My name is TheNamedStruct!"]);
+    }
+
+    #[test]
+    fn derive_adds_type_name() {
+        assert_eq!(TheNamedStruct::type_name(), "TheNamedStruct");
+    }
+
+    #[test]
+    fn field_count_for_each_kind_of_struct() {
+        use procedural_macros::FieldCount;
+
+        #[allow(dead_code)]
+        #[derive(FieldCount)]
+        struct Named {
+            a: u8,
+            b: String,
+            c: Vec<i32>,
+        }
+
+        #[allow(dead_code)]
+        #[derive(FieldCount)]
+        struct Tuple(u8, u16);
+
+        #[derive(FieldCount)]
+        struct Unit;
+
+        assert_eq!(Named::FIELD_COUNT, 3);
+        assert_eq!(Tuple::FIELD_COUNT, 2);
+        assert_eq!(Unit::FIELD_COUNT, 0);
+    }
+
+    #[test]
+    fn builder_with_every_field_set() {
+        use procedural_macros::Builder;
+
+        #[derive(Builder, Debug, PartialEq)]
+        struct Command {
+            executable: String,
+            args: Vec<String>,
+            verbose: bool,
+        }
+
+        let command = Command::builder()
+            .executable(String::from("cargo"))
+            .args(vec![String::from("build")])
+            .verbose(true)
+            .build();
+
+        assert_eq!(
+            command,
+            Ok(Command {
+                executable: String::from("cargo"),
+                args: vec![String::from("build")],
+                verbose: true,
+            })
+        );
+    }
+
+    #[test]
+    fn builder_with_a_missing_field() {
+        use procedural_macros::Builder;
+
+        #[derive(Builder, Debug)]
+        struct Pair<T> {
+            first: T,
+            second: T,
+        }
+
+        let missing = Pair::builder().first(1).build();
+        assert_eq!(missing.unwrap_err(), "field `second` is not set");
+
+        let pair = Pair::builder().second('b').first('a').build().unwrap();
+        assert_eq!((pair.first, pair.second), ('a', 'b'));
+    }
+
+    #[test]
+    fn derive_default2_with_overrides() {
+        use procedural_macros::Default2;
+
+        #[derive(Default2, Debug, PartialEq)]
+        struct Config {
+            #[default2(value = String::from("localhost"))]
+            host: String,
+            #[default2(value = 8080)]
+            port: u16,
+            verbose: bool,
+            retries: Option<u8>,
+        }
+
+        assert_eq!(
+            Config::default(),
+            Config { host: String::from("localhost"), port: 8080, verbose: false, retries: None }
+        );
+    }
+
+    #[test]
+    fn derive_default2_without_overrides() {
+        use procedural_macros::Default2;
+
+        #[derive(Default2, Debug, PartialEq)]
+        struct Plain<T> {
+            count: u32,
+            items: Vec<T>,
+        }
+
+        assert_eq!(Plain::<char>::default(), Plain { count: 0, items: Vec::new() });
+    }
+
+    #[test]
+    fn derive_variant_name() {
+        use procedural_macros::VariantName;
+
+        //The fields are only there to show they are skipped over, nothing reads them.
+        #[derive(VariantName)]
+        #[allow(dead_code)]
+        enum Message<T> {
+            Quit,
+            Write(String),
+            Move { x: i32, y: i32 },
+            Wrapped(T),
+        }
+
+        #[derive(VariantName)]
+        #[allow(dead_code)]
+        enum Never {}
+
+        assert_eq!(Message::<u8>::Quit.variant_name(), "Quit");
+        assert_eq!(Message::<u8>::Write(String::from("hi")).variant_name(), "Write");
+        assert_eq!(Message::<u8>::Move { x: 1, y: 2 }.variant_name(), "Move");
+        assert_eq!(Message::Wrapped(5_u8).variant_name(), "Wrapped");
+    }
+
+    #[test]
+    fn trace_calls_counts_each_call() {
+        use procedural_macros::trace_calls;
+
+        #[trace_calls]
+        fn double(x: u32) -> u32 {
+            x * 2
+        }
+
+        assert_eq!(double_call_count(), 0);
+        assert_eq!(double(2), 4);
+        assert_eq!(double(5), 10);
+        double(0);
+        assert_eq!(double_call_count(), 3);
+
+        //The count is per thread, so a new thread starts over at 0.
+        let other = std::thread::spawn(|| {
+            double(1);
+            double_call_count()
+        });
+        assert_eq!(other.join().unwrap(), 1);
+        assert_eq!(double_call_count(), 3);
+    }
+
+    #[test]
+    fn map_builds_a_hash_map() {
+        use procedural_macros::map;
+        use std::collections::HashMap;
+
+        let scores = map! { "x": 1, "y": 2, "z": 1 + 2 };
+        assert_eq!(scores.len(), 3);
+        assert_eq!(scores["z"], 3);
+
+        let names = map! { <u8, String> 1: String::from("one"), 2: "two".to_string(), };
+        assert_eq!(names.get(&2).map(String::as_str), Some("two"));
+
+        let empty = map! { <char, bool> };
+        assert_eq!(empty, HashMap::new());
+    }
+
+    #[test]
+    fn derived_type_greets_from_a_value() {
+        use procedural_trait::IntoHelloMacro;
+
+        let value = TheNamedStruct;
+        value.greet();
+
+        let mut output = Vec::new();
+        value.greet_to(&mut output).unwrap();
+        let greeting = String::from_utf8(output).unwrap();
+        assert_eq!(greeting, "Hello, Macro! My name is TheNamedStruct!\n");
+    }
+
+    #[test]
+    fn greet_log_collects_greetings_from_threads() {
+        use procedural_trait::greet_log;
+
+        #[derive(HelloMacro)]
+        struct First;
+
+        #[derive(HelloMacro)]
+        struct Second;
+
+        greet_log::set_enabled(true);
+        let handles = vec![
+            std::thread::spawn(First::hello_macro),
+            std::thread::spawn(Second::hello_macro),
+            std::thread::spawn(First::hello_macro),
+        ];
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        greet_log::set_enabled(false);
+
+        //Other tests can greet at the same time, so only these greetings are counted.
+        let log = greet_log::take_log();
+        let count = |name: &str| log.iter().filter(|m| m.ends_with(&format!("{}!", name))).count();
+        assert_eq!(count("First"), 2);
+        assert_eq!(count("Second"), 1);
+    }
+
+    #[test]
+    fn derived_config_greeting() {
+        use procedural_trait::Greeting;
+
+        let configs = [
+            Greeting::default(),
+            Greeting { prefix: String::from("Howdy"), exclaim: false },
+        ];
+
+        let mut output = Vec::new();
+        for cfg in &configs {
+            TheNamedStruct::hello_macro_with_config_to(cfg, &mut output).unwrap();
+        }
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "Hello, TheNamedStruct!\nHowdy, TheNamedStruct.\n"
+        );
+    }
+
+    #[test]
+    fn greet_composes_with_derived_hello_macro() {
+        #[derive(HelloMacro)]
+        struct Greeted;
+
+        procedural_trait::greet::<TheNamedStruct>();
+
+        let mut output = Vec::new();
+        procedural_trait::greet_to::<TheNamedStruct, _>(&mut output).unwrap();
+        procedural_trait::greet_to::<Greeted, _>(&mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "Hello, Macro! My name is TheNamedStruct!\nHello, Macro! My name is Greeted!\n"
+        );
+    }
+}
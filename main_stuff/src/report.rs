@@ -0,0 +1,73 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+//Every section builds one of these instead of printing directly. That way the binary can print
+// the lines while the tests can look at them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SectionReport {
+    pub name: String,
+    pub lines: Vec<String>,
+}
+
+impl SectionReport {
+    pub fn new(name: &str) -> SectionReport {
+        SectionReport {
+            name: name.to_string(),
+            lines: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, line: impl Into<String>) {
+        self.lines.push(line.into());
+    }
+
+    //Adds everything that was written to a writer (such as a Vec<u8>) one line at a time. This is
+    // used for code that only knows how to write to an io::Write.
+    pub fn push_output(&mut self, output: &[u8]) {
+        for line in String::from_utf8_lossy(output).lines() {
+            self.push(line);
+        }
+    }
+
+    //Returns how many lines are exactly equal to `line`.
+    pub fn count(&self, line: &str) -> usize {
+        self.lines.iter().filter(|l| l.as_str() == line).count()
+    }
+}
+
+//Displays one line after another, each ending in a newline, which is exactly what the sections
+// used to println!.
+impl Display for SectionReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for line in &self.lines {
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_output_splits_lines() {
+        let mut report = SectionReport::new("test");
+        report.push("first");
+        report.push_output(b"second\nthird\n");
+
+        assert_eq!(report.lines, vec!["first", "second", "third"]);
+        assert_eq!(report.to_string(), "first\nsecond\nthird\n");
+    }
+
+    #[test]
+    fn count_matches_whole_lines() {
+        let mut report = SectionReport::new("test");
+        report.push("a");
+        report.push("ab");
+        report.push("a");
+
+        assert_eq!(report.count("a"), 2);
+        assert_eq!(report.count("b"), 0);
+    }
+}
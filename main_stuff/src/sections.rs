@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::report::SectionReport;
+use crate::{advanced_traits, advanced_types, functions_and_closures, macros_demo, unsafe_rust};
+
+//The names that can be passed to the binary, in the order that the sections are run when no name
+// is given. A HashMap has no order of its own so the order is kept here.
+pub const SECTION_NAMES: [&str; 5] = ["unsafe", "traits", "types", "closures", "macros"];
+
+//Each run() function is a plain `fn() -> SectionReport` so they can all be stored as function
+// pointers inside of the same map, even though they are different functions.
+pub type Section = fn() -> SectionReport;
+
+pub fn sections() -> HashMap<&'static str, Section> {
+    let mut sections: HashMap<&'static str, Section> = HashMap::new();
+    sections.insert("unsafe", unsafe_rust::run);
+    sections.insert("traits", advanced_traits::run);
+    sections.insert("types", advanced_types::run);
+    sections.insert("closures", functions_and_closures::run);
+    sections.insert("macros", macros_demo::run);
+    sections
+}
+
+#[derive(Debug, PartialEq)]
+pub struct UnknownSection {
+    pub name: String,
+}
+
+impl Display for UnknownSection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown section `{}`, valid options are: {}",
+            self.name,
+            SECTION_NAMES.join(", ")
+        )
+    }
+}
+
+pub fn lookup(name: &str) -> Result<Section, UnknownSection> {
+    sections()
+        .get(name)
+        .copied()
+        .ok_or_else(|| UnknownSection { name: name.to_string() })
+}
+
+pub fn run_all() -> Vec<SectionReport> {
+    let sections = sections();
+    SECTION_NAMES.iter().map(|name| sections[name]()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_name_has_a_section() {
+        let sections = sections();
+
+        assert_eq!(sections.len(), SECTION_NAMES.len());
+        for name in SECTION_NAMES {
+            assert!(sections.contains_key(name), "missing section {name}");
+        }
+    }
+
+    #[test]
+    fn lookup_finds_every_listed_section() {
+        for name in SECTION_NAMES {
+            assert!(lookup(name).is_ok(), "lookup failed for {name}");
+        }
+    }
+
+    #[test]
+    fn run_all_keeps_section_order() {
+        let _guard = unsafe_rust::RUN_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let names: Vec<String> = run_all().into_iter().map(|report| report.name).collect();
+        assert_eq!(names, SECTION_NAMES);
+    }
+
+    #[test]
+    fn lookup_rejects_unknown_names() {
+        let error = lookup("generics").unwrap_err();
+
+        assert_eq!(error, UnknownSection { name: String::from("generics") });
+        assert_eq!(
+            error.to_string(),
+            "unknown section `generics`, valid options are: unsafe, traits, types, closures, macros"
+        );
+    }
+}
@@ -0,0 +1,179 @@
+//By nature it is better for Rust to reject some valid programs than to allow some invalid
+// programs. So unsafe Rust is used to allow the user to override when they know better than
+// the compiler. Also there are certain things such as communicating directly with the OS that
+// Rust cannot do without unsafe mode.
+
+//There are five actions that can be taken in unsafe Rust.
+// 1) Dereference raw pointers.
+// 2) Call an unsafe function or method.
+// 3) Access or modify a mutable static variable.
+// 4) Implement an unsafe trait.
+// 5) Access fields of unions.
+//Rust still has all checking done normally outside of these five features.
+
+use crate::report::SectionReport;
+
+//A buffer that allocates its own memory, see alloc::RawBuffer.
+pub mod alloc;
+//A bump allocator that hands out references, see arena::Arena.
+pub mod arena;
+//Reading and writing integers in byte buffers, see byte_cursor::ByteCursor.
+pub mod byte_cursor;
+//Bounds checked wrappers around ptr::copy() and friends, see copy::copy_slice().
+pub mod copy;
+//A thread safe replacement for a static mut counter, see counter::Counter.
+pub mod counter;
+//Rust functions exported to `C`, see exports::rust_add() and friends.
+pub mod exports;
+//Safe wrappers around functions from the `C` standard library, see ffi::c_sqrt() and friends.
+pub mod ffi;
+//A Vec written from scratch, see growable_array::GrowableArray.
+pub mod growable_array;
+//Viewing values as bytes through unions and pointer casts, see layout::bytes_of().
+pub mod layout;
+//Replacing each element with a function of itself, see map_in_place::map_in_place().
+pub mod map_in_place;
+//A value that is initialized once, safely, from any thread, see once_init::OnceInit.
+pub mod once_init;
+//Splitting one slice into two mutable halves, see pointers::split_at_mut().
+pub mod pointers;
+//Sorting through the `C` qsort() with a Rust comparator, see qsort::sort_with_qsort().
+pub mod qsort;
+//A linked list held together by raw pointers, see raw_list::RawList.
+pub mod raw_list;
+//A fixed capacity queue that wraps around, see ring_buffer::RingBuffer.
+pub mod ring_buffer;
+//A lock built from an atomic flag and an UnsafeCell, see spin_lock::SpinLock.
+pub mod spin_lock;
+//A fixed capacity Vec that lives in an array, see stack_vec::StackVec.
+pub mod stack_vec;
+//A `C` style union behind a tagged, safe API, see unions::Value.
+pub mod unions;
+//Small unsafe building blocks, see unsafe_utils::NonNullBox.
+pub mod unsafe_utils;
+//Reads and writes that the compiler cannot skip, see volatile::Volatile.
+pub mod volatile;
+
+/// An unsafe function (which is essentially a block of unsafe code) must also be called inside
+/// an unsafe block.
+///
+/// # Safety
+///
+/// This function does not actually do anything dangerous, it only exists to show the syntax.
+pub unsafe fn danger() -> &'static str {
+    "Dangerous function called!"
+}
+
+//It is also possible to create a safe abstraction around the unsafe code.
+pub fn danger_two() -> i32 {
+    let mut x = 1;
+    let ptr = &mut x as *mut i32;
+    unsafe { *ptr }
+}
+
+/// They don't go into much detail, but an unsafe trait can be used as well.
+///
+/// # Safety
+///
+/// There are no invariants attached to this trait, it only exists to show the syntax.
+pub unsafe trait Foo {}
+
+//Unions are the final way that unsafe code works. unions are apparently like structs. However,
+// their primary use is to interface with `C` language unions. See unions::Value for a union with
+// a safe wrapper around it.
+
+pub fn run() -> SectionReport {
+    let mut report = SectionReport::new("unsafe");
+
+    //A raw pointer can be created without unsafe code.
+    let mut x = 5;
+
+    //An immutable pointer.
+    let immutable_ptr = &x as *const i32;
+    //A mutable pointer.
+    let mutable_ptr = &mut x as *mut i32;
+
+    //This will create a pointer to an arbitrary memory location.
+    let address = 0x012345usize;
+    let _unknown_if_valid = address as *const i32;
+
+    let mut output = Vec::new();
+    unsafe {
+        crate::print_each!(output;
+            "mut_ptr" => *mutable_ptr,
+            "ptr" => *immutable_ptr,
+        ).expect("writing to a Vec cannot fail");
+
+        //This can and probably will segfault if there is not valid memory at the location.
+        // println!("unknown_ptr: {}", *unknown_if_valid);
+    }
+    report.push_output(&output);
+
+    unsafe {
+        report.push(danger());
+    }
+
+    report.push(format!("Another dangerous function {:?}", danger_two()));
+
+    let mut x = vec![1, 2, 3, 4, 5];
+
+    report.push(format!("Unsafe stuff: {:?}", pointers::split_at_mut(&mut x, 3)));
+
+    report.push(format!("Absolute value of -3 according to C: {}", ffi::c_abs(-3)));
+    for line in ffi::comparisons() {
+        report.push(line);
+    }
+    for line in exports::demo() {
+        report.push(line);
+    }
+
+    let mut numbers = [5, -2, 9, 0];
+    qsort::sort_i32_via_qsort(&mut numbers);
+    report.push(format!("sorted by C qsort: {:?}", numbers));
+
+    //The static mut version needs an unsafe block, the atomic version does not. They are both
+    // bumped once here so they should agree.
+    let static_mut_count = unsafe { counter::increment_static_mut() };
+    let atomic_count = counter::increment();
+    report.push(format!("COUNTER: {}", static_mut_count));
+    report.push(format!("atomic COUNTER: {}", atomic_count));
+    report.push(format!("counters agree: {}", static_mut_count == atomic_count));
+
+    report
+}
+
+//run() bumps the static mut counter, which must never happen from two threads at once, and reads
+// the environment through `C`, which must not happen while a test is changing it. Tests run in
+// parallel, so every test that calls run() or sets an environment variable holds this lock first.
+#[cfg(test)]
+pub(crate) static RUN_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn danger_two_reads_through_pointer() {
+        assert_eq!(danger_two(), 1);
+    }
+
+    #[test]
+    fn report_contains_key_lines() {
+        let report = {
+            let _guard = RUN_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            run()
+        };
+
+        assert_eq!(report.name, "unsafe");
+        assert_eq!(&report.lines[..3], ["mut_ptr: 5", "ptr: 5", "Dangerous function called!"]);
+        assert_eq!(report.count("Unsafe stuff: ([1, 2, 3], [4, 5])"), 1);
+        assert_eq!(report.count("Absolute value of -3 according to C: 3"), 1);
+
+        //Other tests also bump the counters so only their shape can be checked here.
+        for prefix in ["COUNTER: ", "atomic COUNTER: "] {
+            let counter_lines: Vec<_> = report.lines.iter().filter(|l| l.starts_with(prefix)).collect();
+            assert_eq!(counter_lines.len(), 1);
+            assert!(counter_lines[0][prefix.len()..].parse::<isize>().unwrap() >= 1);
+        }
+    }
+}
@@ -0,0 +1,275 @@
+//A buffer that manages its own memory through std::alloc instead of using a Vec. All of the
+// unsafe code lives inside of RawAlloc and RawBuffer, the functions that RawBuffer exposes are
+// all safe.
+//
+//The invariants that the unsafe code relies on are:
+// 1) `ptr` points to an allocation with room for `capacity` values of T. When nothing has been
+//    allocated (capacity 0 or T is zero sized) `ptr` is dangling but still non-null and aligned.
+// 2) The first `len` slots are initialized and the rest are not, `len <= capacity` always.
+// 3) For zero sized types nothing is ever allocated and the capacity is usize::MAX.
+
+use std::alloc::{self, Layout};
+use std::marker::PhantomData;
+use std::mem;
+use std::ptr::{self, NonNull};
+use std::slice;
+
+//The memory for `capacity` values of T without any idea of which of them are initialized, so
+// dropping it only frees the memory. RawBuffer and growable_array::GrowableArray are both built
+// on top of it and are responsible for dropping the values.
+pub(crate) struct RawAlloc<T> {
+    ptr: NonNull<T>,
+    capacity: usize,
+    //Tells the compiler that a RawAlloc owns values of T, which matters for drop checking.
+    _marker: PhantomData<T>,
+}
+
+//Safety: RawAlloc owns its values in the same way that a Vec does, so it can be sent and shared
+// between threads whenever T can.
+unsafe impl<T: Send> Send for RawAlloc<T> {}
+unsafe impl<T: Sync> Sync for RawAlloc<T> {}
+
+impl<T> RawAlloc<T> {
+    const IS_ZST: bool = mem::size_of::<T>() == 0;
+
+    pub(crate) fn new() -> RawAlloc<T> {
+        RawAlloc::with_capacity(0)
+    }
+
+    pub(crate) fn with_capacity(capacity: usize) -> RawAlloc<T> {
+        if Self::IS_ZST || capacity == 0 {
+            return RawAlloc {
+                ptr: NonNull::dangling(),
+                capacity: if Self::IS_ZST { usize::MAX } else { 0 },
+                _marker: PhantomData,
+            };
+        }
+
+        let layout = Layout::array::<T>(capacity).expect("capacity overflow");
+        //Safety: the layout has a non-zero size because T is not zero sized and capacity > 0.
+        let raw = unsafe { alloc::alloc(layout) } as *mut T;
+        let ptr = NonNull::new(raw).unwrap_or_else(|| alloc::handle_alloc_error(layout));
+
+        RawAlloc {
+            ptr,
+            capacity,
+            _marker: PhantomData,
+        }
+    }
+
+    pub(crate) fn ptr(&self) -> *mut T {
+        self.ptr.as_ptr()
+    }
+
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    //Doubles the capacity (starting at 4) with realloc. Layout::array() fails when the total
+    // size would be more than isize::MAX bytes, which is the limit for any allocation.
+    pub(crate) fn grow(&mut self) {
+        //Zero sized types start out with a capacity of usize::MAX, so needing to grow means the
+        // length would overflow.
+        assert!(!Self::IS_ZST, "capacity overflow");
+
+        let new_capacity = if self.capacity == 0 {
+            4
+        } else {
+            self.capacity.checked_mul(2).expect("capacity overflow")
+        };
+        let new_layout = Layout::array::<T>(new_capacity).expect("capacity overflow");
+
+        //Safety: when capacity is 0 there is no old allocation so a new one is made. Otherwise
+        // the old layout is exactly the one that the memory was allocated with.
+        let raw = unsafe {
+            if self.capacity == 0 {
+                alloc::alloc(new_layout)
+            } else {
+                let old_layout = Layout::array::<T>(self.capacity).unwrap();
+                alloc::realloc(self.ptr.as_ptr() as *mut u8, old_layout, new_layout.size())
+            }
+        } as *mut T;
+
+        self.ptr = NonNull::new(raw).unwrap_or_else(|| alloc::handle_alloc_error(new_layout));
+        self.capacity = new_capacity;
+    }
+}
+
+impl<T> Drop for RawAlloc<T> {
+    fn drop(&mut self) {
+        //Nothing was allocated for zero sized types or for an empty capacity.
+        if !Self::IS_ZST && self.capacity != 0 {
+            let layout = Layout::array::<T>(self.capacity).unwrap();
+            //Safety: this is the same layout that the memory was allocated with.
+            unsafe {
+                alloc::dealloc(self.ptr.as_ptr() as *mut u8, layout);
+            }
+        }
+    }
+}
+
+pub struct RawBuffer<T> {
+    buf: RawAlloc<T>,
+    len: usize,
+}
+
+impl<T> RawBuffer<T> {
+    pub fn new() -> RawBuffer<T> {
+        RawBuffer::with_capacity(0)
+    }
+
+    pub fn with_capacity(capacity: usize) -> RawBuffer<T> {
+        RawBuffer {
+            buf: RawAlloc::with_capacity(capacity),
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    pub fn push(&mut self, value: T) {
+        if self.len == self.capacity() {
+            self.buf.grow();
+        }
+
+        //Safety: len < capacity after growing, so the slot is inside of the allocation and is
+        // not initialized yet.
+        unsafe {
+            self.buf.ptr().add(self.len).write(value);
+        }
+        self.len += 1;
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.as_slice().get(index)
+    }
+
+    //Replaces the value at `index`, dropping the old one. Panics if `index` is out of bounds,
+    // the same as indexing into a slice.
+    pub fn set(&mut self, index: usize, value: T) {
+        self.as_mut_slice()[index] = value;
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        //Safety: the first len values are initialized and ptr is non-null and aligned even when
+        // nothing was allocated.
+        unsafe { slice::from_raw_parts(self.buf.ptr(), self.len) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        //Safety: the same as as_slice(), and &mut self guarantees the access is unique.
+        unsafe { slice::from_raw_parts_mut(self.buf.ptr(), self.len) }
+    }
+}
+
+impl<T> Default for RawBuffer<T> {
+    fn default() -> RawBuffer<T> {
+        RawBuffer::new()
+    }
+}
+
+impl<T> Drop for RawBuffer<T> {
+    fn drop(&mut self) {
+        //Safety: the first len values are initialized and are never used again after this.
+        // RawAlloc frees the memory afterwards.
+        unsafe {
+            ptr::drop_in_place(self.as_mut_slice());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    struct DropCounter(Rc<Cell<usize>>);
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn push_past_initial_capacity() {
+        let mut buffer = RawBuffer::with_capacity(2);
+        for i in 0..10 {
+            buffer.push(i * 10);
+        }
+
+        assert_eq!(buffer.len(), 10);
+        assert!(buffer.capacity() >= 10);
+        assert_eq!(buffer.get(0), Some(&0));
+        assert_eq!(buffer.get(9), Some(&90));
+        assert_eq!(buffer.get(10), None);
+        assert_eq!(buffer.as_slice(), [0, 10, 20, 30, 40, 50, 60, 70, 80, 90]);
+    }
+
+    #[test]
+    fn set_and_as_mut_slice_write_values() {
+        let mut buffer = RawBuffer::new();
+        buffer.push(String::from("a"));
+        buffer.push(String::from("b"));
+
+        buffer.set(1, String::from("c"));
+        buffer.as_mut_slice()[0].push('!');
+
+        assert_eq!(buffer.as_slice(), ["a!", "c"]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_out_of_bounds_panics() {
+        let mut buffer = RawBuffer::new();
+        buffer.push(1);
+        buffer.set(1, 2);
+    }
+
+    #[test]
+    fn drop_runs_element_destructors() {
+        let drops = Rc::new(Cell::new(0));
+        let mut buffer = RawBuffer::with_capacity(1);
+        for _ in 0..5 {
+            buffer.push(DropCounter(Rc::clone(&drops)));
+        }
+
+        buffer.set(0, DropCounter(Rc::clone(&drops)));
+        assert_eq!(drops.get(), 1);
+
+        drop(buffer);
+        assert_eq!(drops.get(), 6);
+    }
+
+    #[test]
+    fn zero_sized_types_never_allocate() {
+        let mut buffer = RawBuffer::new();
+        for _ in 0..100 {
+            buffer.push(());
+        }
+
+        assert_eq!(buffer.len(), 100);
+        assert_eq!(buffer.capacity(), usize::MAX);
+        assert_eq!(buffer.get(99), Some(&()));
+    }
+
+    #[test]
+    fn empty_buffer_drops_cleanly() {
+        let buffer: RawBuffer<String> = RawBuffer::new();
+
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.capacity(), 0);
+        assert_eq!(buffer.as_slice(), [] as [String; 0]);
+    }
+}
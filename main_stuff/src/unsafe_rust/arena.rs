@@ -0,0 +1,232 @@
+//A bump allocator. Memory is taken from the operating system in large chunks, and each
+// allocation just moves a pointer forward through the current chunk. Nothing is freed on its own,
+// the chunks are all freed at once when the Arena is dropped. This makes allocating very cheap,
+// and because every reference handed out borrows the Arena, the borrow checker makes sure none of
+// them outlive the memory they point into.
+//
+//Values are never dropped, only their memory is freed. So anything that owns something else,
+// such as a String or an Rc, leaks whatever it owns when it is put into an Arena.
+//
+//The invariants that the unsafe code relies on are:
+// 1) Every chunk in `chunks` was allocated with the layout stored next to it and is never moved
+//    or freed before the Arena is dropped.
+// 2) `current..end` is the unused part of the newest chunk (or both are null before any chunk
+//    exists). Everything before `current` has been handed out and is never handed out again.
+
+use std::alloc::{self, Layout};
+use std::cell::{Cell, RefCell};
+use std::mem;
+use std::ptr::{self, NonNull};
+use std::slice;
+use std::str;
+
+const DEFAULT_CHUNK_SIZE: usize = 4096;
+//Chunks are at least this aligned, types with a bigger alignment are still handled by padding.
+const CHUNK_ALIGN: usize = 16;
+
+pub struct Arena {
+    chunks: RefCell<Vec<(NonNull<u8>, Layout)>>,
+    current: Cell<*mut u8>,
+    end: Cell<*mut u8>,
+    chunk_size: usize,
+}
+
+impl Arena {
+    pub fn new() -> Arena {
+        Arena::with_chunk_size(DEFAULT_CHUNK_SIZE)
+    }
+
+    //Values bigger than the chunk size still fit, they just get a chunk of their own size.
+    pub fn with_chunk_size(chunk_size: usize) -> Arena {
+        Arena {
+            chunks: RefCell::new(Vec::new()),
+            current: Cell::new(ptr::null_mut()),
+            end: Cell::new(ptr::null_mut()),
+            chunk_size: chunk_size.max(1),
+        }
+    }
+
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.borrow().len()
+    }
+
+    //Moves the value into the Arena. Its destructor will never run, see the top of the file.
+    pub fn alloc<T>(&self, value: T) -> &T {
+        let ptr = self.alloc_layout(Layout::new::<T>()).cast::<T>();
+        //Safety: alloc_layout() returned memory that is sized and aligned for a T and that has
+        // not been handed out before. It stays valid for as long as self is borrowed.
+        unsafe {
+            ptr.as_ptr().write(value);
+            &*ptr.as_ptr()
+        }
+    }
+
+    pub fn alloc_str(&self, s: &str) -> &str {
+        let ptr = self.alloc_layout(Layout::for_value(s.as_bytes()));
+        //Safety: the memory has room for s.len() bytes and is not used by anything else. The
+        // bytes are copied from a str, so they are valid UTF-8.
+        unsafe {
+            ptr::copy_nonoverlapping(s.as_ptr(), ptr.as_ptr(), s.len());
+            str::from_utf8_unchecked(slice::from_raw_parts(ptr.as_ptr(), s.len()))
+        }
+    }
+
+    fn alloc_layout(&self, layout: Layout) -> NonNull<u8> {
+        //Zero sized values do not need any memory, any aligned non-null pointer will do.
+        if layout.size() == 0 {
+            let dangling = ptr::without_provenance_mut::<u8>(layout.align());
+            //Safety: the alignment of a Layout is never zero.
+            return unsafe { NonNull::new_unchecked(dangling) };
+        }
+
+        if let Some(ptr) = self.bump(layout) {
+            return ptr;
+        }
+
+        //Enough extra room is asked for that the value fits even after padding for alignment.
+        let size = self.chunk_size.max(layout.size() + layout.align());
+        self.new_chunk(size, layout.align().max(CHUNK_ALIGN));
+        self.bump(layout).expect("a new chunk always has room for the value")
+    }
+
+    //Takes the next aligned piece of the current chunk, or returns None if it does not fit.
+    fn bump(&self, layout: Layout) -> Option<NonNull<u8>> {
+        let current = self.current.get();
+        if current.is_null() {
+            return None;
+        }
+
+        //align_offset() is how far current has to move forward to be aligned for the value.
+        let padding = current.align_offset(layout.align());
+        let available = self.end.get() as usize - current as usize;
+        if padding.checked_add(layout.size())? > available {
+            return None;
+        }
+
+        //Safety: padding + size <= available, so both pointers stay inside of the current chunk.
+        unsafe {
+            let start = current.add(padding);
+            self.current.set(start.add(layout.size()));
+            Some(NonNull::new_unchecked(start))
+        }
+    }
+
+    fn new_chunk(&self, size: usize, align: usize) {
+        let layout = Layout::from_size_align(size, align).expect("chunk size overflow");
+        //Safety: the size is never zero since both chunk_size and the value size are at least 1.
+        let raw = unsafe { alloc::alloc(layout) };
+        let start = NonNull::new(raw).unwrap_or_else(|| alloc::handle_alloc_error(layout));
+
+        self.chunks.borrow_mut().push((start, layout));
+        self.current.set(start.as_ptr());
+        //Safety: one past the end of an allocation is allowed.
+        self.end.set(unsafe { start.as_ptr().add(size) });
+    }
+}
+
+impl Default for Arena {
+    fn default() -> Arena {
+        Arena::new()
+    }
+}
+
+impl Drop for Arena {
+    fn drop(&mut self) {
+        for (start, layout) in mem::take(self.chunks.get_mut()) {
+            //Safety: each chunk was allocated with this layout and nothing can still be borrowing
+            // from the Arena since it is being dropped.
+            unsafe {
+                alloc::dealloc(start.as_ptr(), layout);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[repr(align(64))]
+    #[derive(Debug, PartialEq)]
+    struct Aligned64(u8);
+
+    fn is_aligned<T>(value: &T) -> bool {
+        (value as *const T).is_aligned()
+    }
+
+    #[test]
+    fn mixed_alignments_across_chunks() {
+        let arena = Arena::with_chunk_size(64);
+        let mut bytes = Vec::new();
+        let mut words = Vec::new();
+        let mut wide = Vec::new();
+
+        for i in 0..20_u8 {
+            bytes.push(arena.alloc(i));
+            words.push(arena.alloc(i as u64 * 1000));
+            wide.push(arena.alloc(Aligned64(i)));
+        }
+
+        assert!(arena.chunk_count() > 1);
+        for i in 0..20_u8 {
+            assert_eq!(*bytes[i as usize], i);
+            assert_eq!(*words[i as usize], i as u64 * 1000);
+            assert_eq!(*wide[i as usize], Aligned64(i));
+            assert!(is_aligned(words[i as usize]));
+            assert!(is_aligned(wide[i as usize]));
+        }
+    }
+
+    #[test]
+    fn strings_are_copied_in() {
+        let arena = Arena::with_chunk_size(8);
+        let original = String::from("hello arena");
+
+        let first = arena.alloc_str(&original);
+        let second = arena.alloc_str("x");
+        let empty = arena.alloc_str("");
+        drop(original);
+
+        assert_eq!((first, second, empty), ("hello arena", "x", ""));
+    }
+
+    #[test]
+    fn values_larger_than_a_chunk() {
+        let arena = Arena::with_chunk_size(16);
+        let big = arena.alloc([7_u32; 100]);
+        let small = arena.alloc(1_u8);
+
+        assert_eq!(big.iter().sum::<u32>(), 700);
+        assert_eq!(*small, 1);
+    }
+
+    #[test]
+    fn zero_sized_values() {
+        let arena = Arena::new();
+
+        assert_eq!(arena.alloc(()), &());
+        assert_eq!(arena.chunk_count(), 0);
+    }
+
+    #[test]
+    fn destructors_are_not_run() {
+        //Counts its drops in a Cell instead of owning anything, so nothing is actually leaked
+        // when its destructor is skipped.
+        struct DropCounter<'a>(&'a Cell<usize>);
+
+        impl Drop for DropCounter<'_> {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let drops = Cell::new(0);
+        {
+            let arena = Arena::new();
+            arena.alloc(DropCounter(&drops));
+            drop(DropCounter(&drops));
+        }
+        //Only the one that was dropped normally was counted, the one in the arena never was.
+        assert_eq!(drops.get(), 1);
+    }
+}
@@ -0,0 +1,210 @@
+//Reading numbers out of a buffer of bytes, such as a file header or a network packet. A u32 in the
+// middle of a byte buffer is usually not on a 4 byte boundary, and dereferencing a misaligned
+// `*const u32` is undefined behavior. ptr::read_unaligned() is the version that is allowed to
+// read from any address, which is what makes the reads below sound.
+//
+//Each read checks that enough bytes are left first, so the unsafe blocks never read past the end.
+
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::ptr;
+
+//Returned when a read needs more bytes than are left in the buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnexpectedEof {
+    pub needed: usize,
+    pub remaining: usize,
+}
+
+impl Display for UnexpectedEof {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "needed {} bytes but only {} are left", self.needed, self.remaining)
+    }
+}
+
+impl std::error::Error for UnexpectedEof {}
+
+pub struct ByteCursor<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+//Reads a little endian integer of the given type. The bytes are read as-is with read_unaligned()
+// and then converted from little endian, which does nothing on little endian machines.
+macro_rules! read_le {
+    ( $( $name:ident -> $ty:ty ),* $(,)? ) => {
+        $(
+            pub fn $name(&mut self) -> Result<$ty, UnexpectedEof> {
+                let bytes = self.read_bytes(std::mem::size_of::<$ty>())?;
+                //Safety: read_bytes() returned exactly size_of::<$ty>() bytes, and every bit
+                // pattern is a valid integer. read_unaligned() does not care about alignment.
+                let raw = unsafe { ptr::read_unaligned(bytes.as_ptr() as *const $ty) };
+                Ok(<$ty>::from_le(raw))
+            }
+        )*
+    };
+}
+
+impl<'a> ByteCursor<'a> {
+    pub fn new(bytes: &'a [u8]) -> ByteCursor<'a> {
+        ByteCursor { bytes, position: 0 }
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.bytes.len() - self.position
+    }
+
+    //Moving to the very end is allowed, anything past it is not.
+    pub fn seek(&mut self, position: usize) -> Result<(), UnexpectedEof> {
+        if position > self.bytes.len() {
+            return Err(UnexpectedEof {
+                needed: position - self.position,
+                remaining: self.remaining(),
+            });
+        }
+        self.position = position;
+        Ok(())
+    }
+
+    //The returned slice borrows the underlying buffer for 'a, not the cursor.
+    pub fn read_bytes(&mut self, n: usize) -> Result<&'a [u8], UnexpectedEof> {
+        if n > self.remaining() {
+            return Err(UnexpectedEof {
+                needed: n,
+                remaining: self.remaining(),
+            });
+        }
+
+        let bytes = &self.bytes[self.position..self.position + n];
+        self.position += n;
+        Ok(bytes)
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, UnexpectedEof> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    read_le!(read_u16_le -> u16, read_u32_le -> u32, read_u64_le -> u64, read_i32_le -> i32);
+}
+
+//The other direction, building up a buffer of little endian values.
+#[derive(Debug, Default)]
+pub struct ByteWriter {
+    bytes: Vec<u8>,
+}
+
+impl ByteWriter {
+    pub fn new() -> ByteWriter {
+        ByteWriter::default()
+    }
+
+    pub fn write_u8(&mut self, value: u8) -> &mut ByteWriter {
+        self.bytes.push(value);
+        self
+    }
+
+    pub fn write_u16_le(&mut self, value: u16) -> &mut ByteWriter {
+        self.write_bytes(&value.to_le_bytes())
+    }
+
+    pub fn write_u32_le(&mut self, value: u32) -> &mut ByteWriter {
+        self.write_bytes(&value.to_le_bytes())
+    }
+
+    pub fn write_u64_le(&mut self, value: u64) -> &mut ByteWriter {
+        self.write_bytes(&value.to_le_bytes())
+    }
+
+    pub fn write_i32_le(&mut self, value: i32) -> &mut ByteWriter {
+        self.write_bytes(&value.to_le_bytes())
+    }
+
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> &mut ByteWriter {
+        self.bytes.extend_from_slice(bytes);
+        self
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn into_vec(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_a_header() {
+        let mut writer = ByteWriter::new();
+        writer
+            .write_u8(7)
+            .write_u16_le(0xBEEF)
+            .write_u32_le(0xDEAD_BEEF)
+            .write_u64_le(u64::MAX - 1)
+            .write_i32_le(-42)
+            .write_bytes(b"tail");
+        let bytes = writer.into_vec();
+        assert_eq!(bytes.len(), 1 + 2 + 4 + 8 + 4 + 4);
+
+        let mut cursor = ByteCursor::new(&bytes);
+        assert_eq!(cursor.read_u8(), Ok(7));
+        assert_eq!(cursor.read_u16_le(), Ok(0xBEEF));
+        assert_eq!(cursor.read_u32_le(), Ok(0xDEAD_BEEF));
+        assert_eq!(cursor.read_u64_le(), Ok(u64::MAX - 1));
+        assert_eq!(cursor.read_i32_le(), Ok(-42));
+        assert_eq!(cursor.read_bytes(4), Ok(&b"tail"[..]));
+        assert_eq!(cursor.remaining(), 0);
+    }
+
+    #[test]
+    fn values_are_little_endian() {
+        let mut writer = ByteWriter::new();
+        writer.write_u32_le(0x0102_0304);
+
+        assert_eq!(writer.as_slice(), [4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn eof_at_every_read_size() {
+        let bytes = [0xff_u8; 7];
+        let mut cursor = ByteCursor::new(&bytes);
+        cursor.seek(7).unwrap();
+
+        assert_eq!(cursor.read_u8(), Err(UnexpectedEof { needed: 1, remaining: 0 }));
+        cursor.seek(6).unwrap();
+        assert_eq!(cursor.read_u16_le(), Err(UnexpectedEof { needed: 2, remaining: 1 }));
+        cursor.seek(4).unwrap();
+        assert_eq!(cursor.read_u32_le(), Err(UnexpectedEof { needed: 4, remaining: 3 }));
+        assert_eq!(cursor.read_i32_le(), Err(UnexpectedEof { needed: 4, remaining: 3 }));
+        cursor.seek(0).unwrap();
+        assert_eq!(cursor.read_u64_le(), Err(UnexpectedEof { needed: 8, remaining: 7 }));
+        let error = cursor.read_bytes(8).unwrap_err();
+        assert_eq!(error.to_string(), "needed 8 bytes but only 7 are left");
+
+        //A failed read does not move the cursor.
+        assert_eq!(cursor.position(), 0);
+        assert!(cursor.seek(8).is_err());
+    }
+
+    #[test]
+    fn unaligned_offsets() {
+        let mut bytes = vec![0_u8; 3];
+        bytes.extend_from_slice(&0x1122_3344_5566_7788_u64.to_le_bytes());
+        bytes.extend_from_slice(&0xABCD_u16.to_le_bytes());
+
+        for start in 0..3 {
+            let mut cursor = ByteCursor::new(&bytes[start..]);
+            cursor.seek(3 - start).unwrap();
+            assert_eq!(cursor.read_u64_le(), Ok(0x1122_3344_5566_7788));
+            assert_eq!(cursor.read_u16_le(), Ok(0xABCD));
+        }
+    }
+}
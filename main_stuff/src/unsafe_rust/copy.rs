@@ -0,0 +1,168 @@
+//ptr::copy_nonoverlapping() is memcpy and ptr::copy() is memmove. Both copy raw bytes without
+// checking anything, so a wrong length writes past the end of a buffer. The functions below do all
+// of the checking first and return an error, which leaves the unsafe blocks with nothing that can
+// go wrong.
+//T is limited to Copy so that duplicating the bytes of a value is the same as copying it, and
+// nothing that gets overwritten needs to be dropped.
+
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+use std::ptr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CopyError {
+    //The destination cannot hold everything in the source.
+    DestinationTooShort { needed: usize, available: usize },
+    //The range to copy from is backwards or runs past the end of the slice.
+    InvalidRange { range: Range<usize>, len: usize },
+    //Copying the range to `to` would run past the end of the slice.
+    TargetOutOfBounds { to: usize, count: usize, len: usize },
+}
+
+impl Display for CopyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CopyError::DestinationTooShort { needed, available } => write!(
+                f,
+                "destination holds {} elements but {} need to be copied",
+                available, needed
+            ),
+            CopyError::InvalidRange { range, len } => {
+                write!(f, "range {:?} is not inside of a slice of length {}", range, len)
+            }
+            CopyError::TargetOutOfBounds { to, count, len } => write!(
+                f,
+                "copying {} elements to {} runs past the end of a slice of length {}",
+                count, to, len
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CopyError {}
+
+//Copies all of `src` to the front of `dst` and returns how many elements were copied. The borrow
+// checker already guarantees that a `&[T]` and a `&mut [T]` never overlap, which is exactly the
+// promise that copy_nonoverlapping() needs.
+pub fn copy_slice<T: Copy>(src: &[T], dst: &mut [T]) -> Result<usize, CopyError> {
+    if dst.len() < src.len() {
+        return Err(CopyError::DestinationTooShort {
+            needed: src.len(),
+            available: dst.len(),
+        });
+    }
+
+    //Safety: src is valid for src.len() reads, dst was checked to be valid for that many writes,
+    // and the two cannot overlap.
+    unsafe {
+        ptr::copy_nonoverlapping(src.as_ptr(), dst.as_mut_ptr(), src.len());
+    }
+    Ok(src.len())
+}
+
+//Copies the elements in `from` so that they start at `to`, inside of the same slice. The source
+// and destination can overlap, so this needs ptr::copy(), which copies as if through a temporary
+// buffer.
+pub fn move_within<T: Copy>(
+    slice: &mut [T],
+    from: Range<usize>,
+    to: usize,
+) -> Result<(), CopyError> {
+    let len = slice.len();
+    if from.start > from.end || from.end > len {
+        return Err(CopyError::InvalidRange { range: from, len });
+    }
+
+    let count = from.end - from.start;
+    if to > len || count > len - to {
+        return Err(CopyError::TargetOutOfBounds { to, count, len });
+    }
+
+    let base = slice.as_mut_ptr();
+    //Safety: from.start + count <= len and to + count <= len were both checked above, so both
+    // ranges are inside of the slice.
+    unsafe {
+        ptr::copy(base.add(from.start), base.add(to), count);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copy_exact_size() {
+        let src = [1, 2, 3];
+        let mut dst = [0; 3];
+
+        assert_eq!(copy_slice(&src, &mut dst), Ok(3));
+        assert_eq!(dst, [1, 2, 3]);
+    }
+
+    #[test]
+    fn copy_into_a_longer_destination() {
+        let mut dst = ['-'; 4];
+
+        assert_eq!(copy_slice(&['a', 'b'], &mut dst), Ok(2));
+        assert_eq!(dst, ['a', 'b', '-', '-']);
+    }
+
+    #[test]
+    fn copy_into_a_short_destination() {
+        let mut dst = [0; 2];
+        let error = copy_slice(&[1, 2, 3], &mut dst).unwrap_err();
+
+        assert_eq!(error, CopyError::DestinationTooShort { needed: 3, available: 2 });
+        assert_eq!(error.to_string(), "destination holds 2 elements but 3 need to be copied");
+        assert_eq!(dst, [0, 0]);
+    }
+
+    #[test]
+    fn move_within_overlapping_forward() {
+        let mut values = [1, 2, 3, 4, 5];
+
+        assert_eq!(move_within(&mut values, 0..3, 2), Ok(()));
+        assert_eq!(values, [1, 2, 1, 2, 3]);
+    }
+
+    #[test]
+    fn move_within_overlapping_backward() {
+        let mut values = [1, 2, 3, 4, 5];
+
+        assert_eq!(move_within(&mut values, 2..5, 0), Ok(()));
+        assert_eq!(values, [3, 4, 5, 4, 5]);
+    }
+
+    #[test]
+    fn move_within_empty_range() {
+        let mut values = [1, 2, 3];
+
+        assert_eq!(move_within(&mut values, 1..1, 3), Ok(()));
+        assert_eq!(move_within(&mut values, 3..3, 0), Ok(()));
+        assert_eq!(values, [1, 2, 3]);
+    }
+
+    #[test]
+    fn move_within_errors() {
+        let mut values = [1, 2, 3];
+
+        assert_eq!(
+            move_within(&mut values, 1..4, 0),
+            Err(CopyError::InvalidRange { range: 1..4, len: 3 })
+        );
+        #[allow(clippy::reversed_empty_ranges)]
+        let backwards = 2..1;
+        assert!(matches!(
+            move_within(&mut values, backwards, 0),
+            Err(CopyError::InvalidRange { .. })
+        ));
+        assert_eq!(
+            move_within(&mut values, 0..2, 2),
+            Err(CopyError::TargetOutOfBounds { to: 2, count: 2, len: 3 })
+        );
+        assert!(move_within(&mut values, 0..0, 4).is_err());
+        assert_eq!(values, [1, 2, 3]);
+    }
+}
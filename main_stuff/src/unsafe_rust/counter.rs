@@ -0,0 +1,189 @@
+//A counter that can be shared between threads without any unsafe code. An atomic integer is
+// changed with a single instruction that cannot be interrupted halfway, so two threads
+// incrementing at the same time can never lose an update the way they can with a static mut.
+
+use std::sync::atomic::{AtomicIsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Instant;
+
+pub struct Counter {
+    value: AtomicIsize,
+}
+
+//A value read out of a Counter along with when it was read. The value is copied out by a single
+// load, so it is one the counter really had, even if other threads have changed it since.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CounterSnapshot {
+    pub value: isize,
+    pub taken_at: Instant,
+}
+
+impl Counter {
+    //A const fn so that a Counter can be used to initialize a static.
+    pub const fn new() -> Counter {
+        Counter {
+            value: AtomicIsize::new(0),
+        }
+    }
+
+    //Adds one and returns the new value.
+    pub fn increment(&self) -> isize {
+        self.value.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    pub fn get(&self) -> isize {
+        self.value.load(Ordering::SeqCst)
+    }
+
+    pub fn reset(&self) {
+        self.value.store(0, Ordering::SeqCst);
+    }
+
+    pub fn snapshot(&self) -> CounterSnapshot {
+        CounterSnapshot {
+            value: self.get(),
+            taken_at: Instant::now(),
+        }
+    }
+}
+
+impl Default for Counter {
+    fn default() -> Counter {
+        Counter::new()
+    }
+}
+
+//Unlike a static mut this does not need to be mutable, the atomic takes care of that through a
+// shared reference.
+pub static COUNTER: Counter = Counter::new();
+
+pub fn increment() -> isize {
+    COUNTER.increment()
+}
+
+pub fn get() -> isize {
+    COUNTER.get()
+}
+
+pub fn reset() {
+    COUNTER.reset();
+}
+
+//The safe way to read the global counter, compare with reading UNSYNCHRONIZED_COUNTER below.
+pub fn read_static_counter() -> CounterSnapshot {
+    COUNTER.snapshot()
+}
+
+//Spawns `threads` threads that each increment one Counter `per_thread` times and returns where it
+// ended up. The counter is not a static here, so each thread gets a clone of an Arc pointing at
+// it. Arc keeps it alive until the last thread is done, and the atomic inside is what makes the
+// increments safe to share.
+pub fn parallel_increment(threads: usize, per_thread: usize) -> isize {
+    let counter = Arc::new(Counter::new());
+
+    let handles: Vec<_> = (0..threads)
+        .map(|_| {
+            let counter = Arc::clone(&counter);
+            thread::spawn(move || {
+                for _ in 0..per_thread {
+                    counter.increment();
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().expect("an incrementing thread panicked");
+    }
+
+    counter.get()
+}
+
+//Rust supports static variables as well. The difference between const variables and static
+// variables is that static variables have a fixed memory address. The static mut variable
+// cannot changed unless unsafe code is used. It is kept here to compare against the atomic one.
+static mut UNSYNCHRONIZED_COUNTER: isize = 0;
+
+/// Calls to access the static mut variable must be done inside the unsafe block. The value is
+/// copied out instead of borrowed because a reference to a static mut is itself dangerous.
+///
+/// # Safety
+///
+/// This must not be called from more than one thread at the same time. Two threads doing
+/// `+= 1` together is a data race, which is undefined behavior.
+pub unsafe fn increment_static_mut() -> isize {
+    UNSYNCHRONIZED_COUNTER += 1;
+    UNSYNCHRONIZED_COUNTER
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn increment_get_and_reset() {
+        let counter = Counter::new();
+
+        assert_eq!(counter.increment(), 1);
+        assert_eq!(counter.increment(), 2);
+        assert_eq!(counter.get(), 2);
+
+        counter.reset();
+        assert_eq!(counter.get(), 0);
+    }
+
+    //Takes RUN_LOCK because COUNTER is shared with run() and the other tests that reset or bump it.
+    fn lock_global_counter() -> std::sync::MutexGuard<'static, ()> {
+        crate::unsafe_rust::RUN_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    #[test]
+    fn increments_from_many_threads_are_not_lost() {
+        let _guard = lock_global_counter();
+        reset();
+
+        thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    for _ in 0..10_000 {
+                        increment();
+                    }
+                });
+            }
+        });
+
+        assert_eq!(get(), 80_000);
+    }
+
+    //With a static mut and `+= 1` some of these would be lost, since two threads can read the same
+    // old value and both write back the same new one.
+    #[test]
+    fn parallel_increment_loses_nothing() {
+        assert_eq!(parallel_increment(8, 5_000), 40_000);
+        assert_eq!(parallel_increment(1, 3), 3);
+        assert_eq!(parallel_increment(0, 100), 0);
+    }
+
+    #[test]
+    fn snapshots_are_ordered() {
+        let counter = Counter::new();
+
+        let before = counter.snapshot();
+        counter.increment();
+        let after = counter.snapshot();
+
+        assert_eq!(before.value, 0);
+        assert_eq!(after.value, 1);
+        assert!(after.value > before.value);
+        assert!(after.taken_at >= before.taken_at);
+    }
+
+    #[test]
+    fn global_counter_counts_up() {
+        let _guard = lock_global_counter();
+        let first = increment();
+        assert!(get() >= first);
+        assert!(read_static_counter().value >= first);
+    }
+}
@@ -0,0 +1,178 @@
+//FFI also works the other way around, Rust functions can be called from `C`. `extern "C"` makes
+// the function use the `C` calling convention and #[no_mangle] keeps the compiler from renaming
+// the symbol, so that `C` code can find it by the name `rust_add`.
+//
+//Two rules have to be kept at the boundary:
+// 1) `C` cannot check anything, so every pointer and length that comes in has to be checked.
+// 2) A panic must never unwind into `C` code. Unwinding out of an `extern "C"` function aborts the
+//    whole process, so each function catches panics itself and returns an error code instead.
+
+use std::panic::{self, UnwindSafe};
+use std::slice;
+
+pub const OK: i32 = 0;
+pub const ERR_NULL_POINTER: i32 = -1;
+pub const ERR_TOO_LONG: i32 = -2;
+pub const ERR_PANIC: i32 = -3;
+
+//Runs `f` and turns a panic into ERR_PANIC. This is the one place that catch_unwind is needed,
+// each exported function goes through it.
+pub fn catch_panic<F: FnOnce() -> i32 + UnwindSafe>(f: F) -> i32 {
+    panic::catch_unwind(f).unwrap_or(ERR_PANIC)
+}
+
+//Adding can overflow, which panics in debug builds, so the sum wraps around the same way that
+// `C` ints do in practice.
+#[no_mangle]
+pub extern "C" fn rust_add(a: i32, b: i32) -> i32 {
+    a.wrapping_add(b)
+}
+
+/// Fills the buffer with the repeating alphabet and returns how many bytes were written, or a
+/// negative error code.
+///
+/// # Safety
+///
+/// If `ptr` is not null it must point to `len` bytes that are valid for writes and not used
+/// anywhere else for the duration of the call.
+#[no_mangle]
+pub unsafe extern "C" fn rust_fill_buffer(ptr: *mut u8, len: usize) -> i32 {
+    if ptr.is_null() {
+        return ERR_NULL_POINTER;
+    }
+    //The number of bytes written is returned as an i32, so a longer buffer cannot be reported.
+    let Ok(written) = i32::try_from(len) else {
+        return ERR_TOO_LONG;
+    };
+
+    //A `&mut [u8]` is not UnwindSafe, since a panic could leave it half written. The raw pointer
+    // is, so the slice is made inside of the closure instead.
+    catch_panic(move || {
+        //Safety: ptr is not null and the caller promises it is valid for len bytes.
+        let buffer = unsafe { slice::from_raw_parts_mut(ptr, len) };
+        for (i, byte) in buffer.iter_mut().enumerate() {
+            *byte = b'a' + (i % 26) as u8;
+        }
+        written
+    })
+}
+
+/// Divides `a` by `b`, writing the result to `out`. Dividing by zero panics in Rust, which is
+/// caught and returned as ERR_PANIC rather than checked up front, to show the panic path.
+///
+/// # Safety
+///
+/// If `out` is not null it must be valid for writing an i32.
+#[no_mangle]
+pub unsafe extern "C" fn rust_divide(a: i32, b: i32, out: *mut i32) -> i32 {
+    if out.is_null() {
+        return ERR_NULL_POINTER;
+    }
+
+    catch_panic(|| {
+        let result = a / b;
+        //Safety: out is not null and the caller promises it is valid for writes.
+        unsafe {
+            *out = result;
+        }
+        OK
+    })
+}
+
+//Stands in for a `C` caller. `C` only ever sees plain function pointers, so the exported
+// functions are passed in as `extern "C" fn` pointers and called through those.
+pub fn call_like_c(
+    add: extern "C" fn(i32, i32) -> i32,
+    fill: unsafe extern "C" fn(*mut u8, usize) -> i32,
+    divide: unsafe extern "C" fn(i32, i32, *mut i32) -> i32,
+) -> Vec<String> {
+    let mut lines = Vec::new();
+    lines.push(format!("rust_add(2, 3) from a C caller: {}", add(2, 3)));
+
+    let mut buffer = [0u8; 5];
+    //Safety: the buffer is 5 bytes long and only used by this call.
+    let written = unsafe { fill(buffer.as_mut_ptr(), buffer.len()) };
+    lines.push(format!(
+        "rust_fill_buffer wrote {} bytes: {}",
+        written,
+        String::from_utf8_lossy(&buffer)
+    ));
+
+    let mut quotient = 0;
+    //Safety: quotient is a valid i32 to write to.
+    let status = unsafe { divide(7, 2, &mut quotient) };
+    lines.push(format!("rust_divide(7, 2) returned status {} and {}", status, quotient));
+
+    lines
+}
+
+pub fn demo() -> Vec<String> {
+    call_like_c(rust_add, rust_fill_buffer, rust_divide)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ptr;
+
+    const ADD: extern "C" fn(i32, i32) -> i32 = rust_add;
+    const FILL: unsafe extern "C" fn(*mut u8, usize) -> i32 = rust_fill_buffer;
+    const DIVIDE: unsafe extern "C" fn(i32, i32, *mut i32) -> i32 = rust_divide;
+
+    #[test]
+    fn add_through_a_function_pointer() {
+        assert_eq!(ADD(2, 3), 5);
+        assert_eq!(ADD(i32::MAX, 1), i32::MIN);
+    }
+
+    #[test]
+    fn fill_buffer_through_a_function_pointer() {
+        let mut buffer = [0u8; 30];
+        let written = unsafe { FILL(buffer.as_mut_ptr(), buffer.len()) };
+
+        assert_eq!(written, 30);
+        assert_eq!(&buffer[..3], b"abc");
+        assert_eq!(&buffer[26..], b"abcd");
+    }
+
+    #[test]
+    fn fill_buffer_checks_its_arguments() {
+        assert_eq!(unsafe { FILL(ptr::null_mut(), 10) }, ERR_NULL_POINTER);
+
+        //The length check happens before the pointer is used, so a dangling pointer with a huge
+        // length is never written to.
+        let dangling = ptr::NonNull::<u8>::dangling().as_ptr();
+        assert_eq!(unsafe { FILL(dangling, usize::MAX) }, ERR_TOO_LONG);
+        assert_eq!(unsafe { FILL(dangling, 0) }, 0);
+    }
+
+    #[test]
+    fn divide_writes_the_result() {
+        let mut out = 0;
+
+        assert_eq!(unsafe { DIVIDE(9, 3, &mut out) }, OK);
+        assert_eq!(out, 3);
+        assert_eq!(unsafe { DIVIDE(9, 3, ptr::null_mut()) }, ERR_NULL_POINTER);
+    }
+
+    #[test]
+    fn panic_becomes_an_error_code() {
+        let mut out = -1;
+
+        assert_eq!(unsafe { DIVIDE(1, 0, &mut out) }, ERR_PANIC);
+        assert_eq!(out, -1);
+        assert_eq!(catch_panic(|| panic!("boom")), ERR_PANIC);
+    }
+
+    #[test]
+    fn demo_calls_each_function() {
+        assert_eq!(
+            demo(),
+            [
+                "rust_add(2, 3) from a C caller: 5",
+                "rust_fill_buffer wrote 5 bytes: abcde",
+                "rust_divide(7, 2) returned status 0 and 3",
+            ]
+        );
+    }
+}
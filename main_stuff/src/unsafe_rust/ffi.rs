@@ -0,0 +1,359 @@
+//Different language functions can be called from inside Rust. Below declares a few functions
+// from the `C` standard library. These calls must always be done inside unsafe blocks, because
+// Rust cannot check anything about code written in another language.
+//Each function gets a safe Rust wrapper that deals with whatever the `C` version does not: inputs
+// that are undefined behavior, integer widths that change between platforms, and errors.
+
+use std::cmp::Ordering;
+use std::ffi::{CStr, CString, NulError};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::os::raw::{c_char, c_double, c_int, c_long};
+use std::ptr::NonNull;
+
+use procedural_macros::unsafe_wrapped;
+
+extern "C" {
+    fn abs(input: c_int) -> c_int;
+    fn labs(input: c_long) -> c_long;
+    fn fabs(input: c_double) -> c_double;
+    fn sqrt(input: c_double) -> c_double;
+    fn pow(base: c_double, exponent: c_double) -> c_double;
+    fn strlen(s: *const c_char) -> usize;
+    fn strcmp(a: *const c_char, b: *const c_char) -> c_int;
+    fn getenv(name: *const c_char) -> *const c_char;
+}
+
+//A safe wrapper around the `C` abs() function so callers do not need an unsafe block.
+//abs(i32::MIN) is undefined behavior in `C` because the result does not fit in an int. Instead
+// i32::MIN is bumped up to i32::MIN + 1 first so the result is i32::MAX, which is the same thing
+// that Rust's i32::saturating_abs() returns.
+pub fn c_abs(input: i32) -> i32 {
+    let input = input.max(i32::MIN + 1);
+    unsafe { abs(input) }
+}
+
+//A safe wrapper around the `C` labs() function. A `C` long is not the same size everywhere, it is
+// 64 bits on Linux and macOS but only 32 bits on Windows, so the input is first clamped into the
+// range of c_long. The lower end of that range is c_long::MIN + 1 because labs(c_long::MIN) is
+// undefined behavior in `C`. The result of this is that anything outside of the range returns
+// c_long::MAX instead of the real absolute value.
+//These casts do nothing on platforms where c_long is already an i64, which clippy points out.
+#[allow(clippy::unnecessary_cast)]
+const C_LONG_MIN: i64 = c_long::MIN as i64;
+#[allow(clippy::unnecessary_cast)]
+const C_LONG_MAX: i64 = c_long::MAX as i64;
+
+pub fn c_labs(input: i64) -> i64 {
+    let clamped = input.clamp(C_LONG_MIN + 1, C_LONG_MAX) as c_long;
+    unsafe { labs(clamped) as i64 }
+}
+
+//fabs() is fine with any input, including NaN and the infinities, so the whole body can be
+// wrapped in unsafe by the #[unsafe_wrapped] attribute from procedural_macros.
+#[unsafe_wrapped]
+pub fn c_fabs(input: f64) -> f64 {
+    fabs(input)
+}
+
+//sqrt() of a negative number is outside of its domain. In `C` that quietly returns NaN and sets
+// errno, here it is turned into an error before `C` is ever called.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DomainError {
+    pub function: &'static str,
+    pub input: f64,
+}
+
+impl Display for DomainError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is outside of the domain of {}()", self.input, self.function)
+    }
+}
+
+impl std::error::Error for DomainError {}
+
+//NaN is passed through to `C` and comes back as NaN, the same as f64::sqrt(). -0.0 is not less
+// than zero so it is allowed and returns -0.0.
+pub fn c_sqrt(input: f64) -> Result<f64, DomainError> {
+    if input < 0.0 {
+        return Err(DomainError { function: "sqrt", input });
+    }
+
+    Ok(unsafe { sqrt(input) })
+}
+
+pub fn c_pow(base: f64, exponent: f64) -> f64 {
+    unsafe { pow(base, exponent) }
+}
+
+//`C` strings are a pointer to bytes that end with a 0 (NUL) byte, while Rust strings carry their
+// length and can contain 0 bytes anywhere. CString makes a copy with the NUL added at the end,
+// which fails with a NulError if the string already has a 0 byte inside of it, because `C` would
+// think the string ended there.
+//strlen() counts bytes and not characters, so multi-byte UTF-8 characters count more than once,
+// the same as str::len().
+pub fn c_strlen(s: &str) -> Result<usize, NulError> {
+    let c_string = CString::new(s)?;
+    //Safety: the pointer is to a NUL terminated string that lives until the end of this function.
+    Ok(unsafe { strlen(c_string.as_ptr()) })
+}
+
+//strcmp() returns a negative number, zero or a positive number, which is turned into an Ordering.
+// It compares bytes, so for UTF-8 the result is the same as comparing the two strs in Rust.
+pub fn c_strcmp(a: &str, b: &str) -> Result<Ordering, NulError> {
+    let a = CString::new(a)?;
+    let b = CString::new(b)?;
+    //Safety: both pointers are to NUL terminated strings that live until the end of this function.
+    let result = unsafe { strcmp(a.as_ptr(), b.as_ptr()) };
+    Ok(result.cmp(&0))
+}
+
+//A `C` string owned through a raw pointer, the way it would be while `C` code holds onto it.
+// CString::into_raw() hands the memory over as a plain pointer and Rust stops tracking it, so it
+// has to be given back with CString::from_raw() exactly once or it leaks (never) or is freed
+// twice (more than once). Drop does that here.
+pub struct OwnedCString {
+    ptr: NonNull<c_char>,
+}
+
+impl OwnedCString {
+    pub fn new(s: &str) -> Result<OwnedCString, NulError> {
+        let raw = CString::new(s)?.into_raw();
+        Ok(OwnedCString {
+            //into_raw() never returns null.
+            ptr: NonNull::new(raw).expect("CString::into_raw returned null"),
+        })
+    }
+
+    //The pointer that would be passed to `C`. It is only valid while self is alive.
+    pub fn as_ptr(&self) -> *const c_char {
+        self.ptr.as_ptr()
+    }
+
+    pub fn as_c_str(&self) -> &CStr {
+        //Safety: the pointer came from CString::into_raw() so it is NUL terminated, and it lives
+        // as long as self.
+        unsafe { CStr::from_ptr(self.as_ptr()) }
+    }
+
+    //Takes ownership back from the raw pointer without freeing it.
+    pub fn into_c_string(self) -> CString {
+        let ptr = self.ptr.as_ptr();
+        //self must not run Drop, otherwise the memory would be freed while the CString still
+        // owns it.
+        std::mem::forget(self);
+        //Safety: the pointer came from CString::into_raw() and has not been reclaimed yet.
+        unsafe { CString::from_raw(ptr) }
+    }
+}
+
+impl Drop for OwnedCString {
+    fn drop(&mut self) {
+        //Safety: the pointer came from CString::into_raw(), and Drop runs at most once.
+        unsafe {
+            drop(CString::from_raw(self.ptr.as_ptr()));
+        }
+    }
+}
+
+//Looks up an environment variable. getenv() returns NULL when the variable is not set, which
+// becomes None. Names that contain a NUL byte cannot be passed to `C` and names that contain '='
+// can never be set, so both of those return None without calling `C` at all. The value is not
+// guaranteed to be UTF-8, so any invalid bytes are replaced with U+FFFD.
+//One thing this wrapper cannot fix is that getenv() is not thread safe. If another thread calls
+// env::set_var() while getenv() is reading, the string can be freed out from under it. Rust's own
+// env::var() avoids that with a lock that `C` code does not know about, which is also why
+// set_var() became an unsafe function in the 2024 edition.
+pub fn c_getenv(name: &str) -> Option<String> {
+    if name.contains('=') {
+        return None;
+    }
+    let name = CString::new(name).ok()?;
+
+    //Safety: the name is NUL terminated and lives until the end of the function. The returned
+    // pointer is either null or points to a NUL terminated string owned by the environment, which
+    // is copied into a String straight away.
+    unsafe {
+        let value = getenv(name.as_ptr());
+        if value.is_null() {
+            None
+        } else {
+            Some(CStr::from_ptr(value).to_string_lossy().into_owned())
+        }
+    }
+}
+
+//Lines comparing each wrapper against the Rust standard library version, used by the demo.
+pub fn comparisons() -> Vec<String> {
+    vec![
+        format!("abs(-3): C {} Rust {}", c_abs(-3), (-3_i32).abs()),
+        format!("labs(-5000000000): C {} Rust {}", c_labs(-5_000_000_000), (-5_000_000_000_i64).abs()),
+        format!("fabs(-2.5): C {} Rust {}", c_fabs(-2.5), (-2.5_f64).abs()),
+        format!("sqrt(2): C {} Rust {}", c_sqrt(2.0).unwrap(), 2.0_f64.sqrt()),
+        format!("pow(2, 10): C {} Rust {}", c_pow(2.0, 10.0), 2.0_f64.powf(10.0)),
+        format!("sqrt(-1): C {}", c_sqrt(-1.0).unwrap_err()),
+        format!("getenv(PATH) matches env::var: {}", c_getenv("PATH") == std::env::var("PATH").ok()),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //Miri cannot call into C. It only emulates a few libc functions itself (strlen, getenv and
+    // fabs among them), so the tests that call anything else are skipped when running under it.
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn c_abs_matches_rust_abs() {
+        for input in [-3, 0, 7, -1024, i32::MAX] {
+            assert_eq!(c_abs(input), input.abs());
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn c_abs_of_min_saturates() {
+        assert_eq!(c_abs(i32::MIN), i32::MAX);
+        assert_eq!(c_abs(i32::MIN), i32::MIN.saturating_abs());
+    }
+
+    #[unsafe_wrapped]
+    fn abs_through_attribute(input: i32) -> i32 {
+        let positive = abs(input);
+        positive.max(abs(0))
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn unsafe_wrapped_calls_extern_functions() {
+        assert_eq!(abs_through_attribute(-3), 3);
+        assert_eq!(abs_through_attribute(4), 4);
+        assert_eq!(c_fabs(-2.5), 2.5);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn c_labs_matches_rust_abs() {
+        let values = [0, 1, -1, -3, 123_456, -987_654_321, C_LONG_MAX, C_LONG_MIN + 1];
+        for input in values {
+            assert_eq!(c_labs(input), input.abs());
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn c_labs_clamps_out_of_range_input() {
+        assert_eq!(c_labs(i64::MIN), C_LONG_MAX);
+        assert_eq!(c_labs(C_LONG_MIN), C_LONG_MAX);
+    }
+
+    #[test]
+    fn c_fabs_matches_rust_abs() {
+        for input in [0.0, -0.0, 1.5, -2.25, f64::MIN, f64::NEG_INFINITY] {
+            assert_eq!(c_fabs(input), input.abs());
+        }
+        assert!(c_fabs(f64::NAN).is_nan());
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn c_sqrt_matches_rust_sqrt() {
+        for input in [0.0, -0.0, 1.0, 2.0, 144.0, 1e300, f64::INFINITY] {
+            assert_eq!(c_sqrt(input), Ok(input.sqrt()));
+        }
+        assert!(c_sqrt(f64::NAN).unwrap().is_nan());
+    }
+
+    #[test]
+    fn c_sqrt_rejects_negative_input() {
+        let error = c_sqrt(-4.0).unwrap_err();
+
+        assert_eq!(error, DomainError { function: "sqrt", input: -4.0 });
+        assert_eq!(error.to_string(), "-4 is outside of the domain of sqrt()");
+        assert!(c_sqrt(f64::NEG_INFINITY).is_err());
+    }
+
+    #[test]
+    fn c_strlen_counts_bytes() {
+        assert_eq!(c_strlen("hello"), Ok(5));
+        assert_eq!(c_strlen(""), Ok(0));
+        //'é' is 2 bytes and '🦀' is 4 bytes in UTF-8.
+        assert_eq!(c_strlen("héllo 🦀"), Ok("héllo 🦀".len()));
+        assert_eq!(c_strlen("héllo 🦀"), Ok(11));
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn c_strcmp_matches_str_ordering() {
+        let pairs = [
+            ("abc", "abd"),
+            ("abc", "abc"),
+            ("b", "a"),
+            ("", "a"),
+            ("", ""),
+            ("é", "e"),
+            ("🦀", "z"),
+        ];
+        for (a, b) in pairs {
+            assert_eq!(c_strcmp(a, b), Ok(a.cmp(b)), "comparing {a:?} and {b:?}");
+        }
+    }
+
+    #[test]
+    fn interior_nul_is_rejected() {
+        assert_eq!(c_strlen("ab\0cd").unwrap_err().nul_position(), 2);
+        assert!(c_strcmp("ok", "\0").is_err());
+        assert!(c_strcmp("\0", "ok").is_err());
+        assert!(OwnedCString::new("a\0").is_err());
+    }
+
+    #[test]
+    fn owned_c_string_can_be_read_through_its_pointer() {
+        let owned = OwnedCString::new("héllo").unwrap();
+
+        assert_eq!(unsafe { strlen(owned.as_ptr()) }, 6);
+        assert_eq!(owned.as_c_str().to_str(), Ok("héllo"));
+    }
+
+    #[test]
+    fn owned_c_string_is_reclaimed_once() {
+        //Dropping frees the memory through Drop.
+        drop(OwnedCString::new("dropped").unwrap());
+
+        //into_c_string() hands the memory to a CString instead, which frees it when it is
+        // dropped. Running this under Miri would report a double free or a leak if either path
+        // got it wrong.
+        let reclaimed = OwnedCString::new("reclaimed").unwrap().into_c_string();
+        assert_eq!(reclaimed.to_str(), Ok("reclaimed"));
+
+        let empty = OwnedCString::new("").unwrap();
+        assert_eq!(empty.as_c_str().to_bytes(), b"");
+    }
+
+    //Everything that touches the environment is kept inside of this one test so that set_var()
+    // never runs while another test is inside of getenv(). The demo also calls getenv(), so the
+    // same lock that the demo tests hold is taken here.
+    #[test]
+    fn c_getenv_reads_the_environment() {
+        let _guard = crate::unsafe_rust::RUN_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        std::env::set_var("CHAPTER_19_FFI_TEST", "héllo");
+
+        assert_eq!(c_getenv("CHAPTER_19_FFI_TEST").as_deref(), Some("héllo"));
+        assert_eq!(c_getenv("CHAPTER_19_FFI_TEST_NOT_SET"), None);
+        assert_eq!(c_getenv("PATH"), std::env::var("PATH").ok());
+
+        assert_eq!(c_getenv("CHAPTER_19\0FFI_TEST"), None);
+        assert_eq!(c_getenv("CHAPTER_19_FFI_TEST=héllo"), None);
+        assert_eq!(c_getenv(""), None);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn c_pow_matches_rust_powf() {
+        let inputs = [(2.0, 10.0), (9.0, 0.5), (-2.0, 3.0), (5.0, 0.0), (0.0, 0.0), (10.0, -2.0)];
+        for (base, exponent) in inputs {
+            assert_eq!(c_pow(base, exponent), f64::powf(base, exponent));
+        }
+    }
+}
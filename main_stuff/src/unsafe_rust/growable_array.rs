@@ -0,0 +1,350 @@
+//A Vec written from scratch on top of NonNull and the std::alloc API, following the same layout
+// as the Vec in the Rustonomicon. The memory handling is the alloc::RawAlloc that RawBuffer also
+// uses, so that both GrowableArray and its owning iterator can share it, while GrowableArray is
+// responsible for which slots are initialized.
+//
+//The invariants that the unsafe code relies on are:
+// 1) RawAlloc::ptr() points to an allocation with room for `capacity` values of T, or is dangling
+//    (but non-null and aligned) when capacity is 0 or T is zero sized.
+// 2) In GrowableArray the slots `[0, len)` are initialized and the rest are not.
+// 3) In IntoIter the slots `[start, end)` are initialized and have not been handed out yet.
+// 4) Zero sized types never allocate and have a capacity of usize::MAX.
+
+use crate::unsafe_rust::alloc::RawAlloc;
+use std::mem::ManuallyDrop;
+use std::ops::{Deref, DerefMut};
+use std::ptr;
+use std::slice;
+
+pub struct GrowableArray<T> {
+    buf: RawAlloc<T>,
+    len: usize,
+}
+
+impl<T> GrowableArray<T> {
+    pub fn new() -> GrowableArray<T> {
+        GrowableArray {
+            buf: RawAlloc::new(),
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    fn ptr(&self) -> *mut T {
+        self.buf.ptr()
+    }
+
+    pub fn push(&mut self, value: T) {
+        if self.len == self.capacity() {
+            self.buf.grow();
+        }
+
+        //Safety: len < capacity so the slot is allocated and not initialized.
+        unsafe {
+            self.ptr().add(self.len).write(value);
+        }
+        self.len += 1;
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+        //Safety: the slot at the old last index was initialized, and lowering len first means it
+        // is treated as uninitialized from now on so it cannot be read twice.
+        unsafe { Some(self.ptr().add(self.len).read()) }
+    }
+
+    //Inserts the value at `index`, moving everything after it one slot to the right. Panics if
+    // `index` is greater than the length.
+    pub fn insert(&mut self, index: usize, value: T) {
+        assert!(
+            index <= self.len,
+            "insertion index (is {}) should be <= len (is {})",
+            index,
+            self.len
+        );
+        if self.len == self.capacity() {
+            self.buf.grow();
+        }
+
+        //Safety: there is room for one more value. ptr::copy is used instead of
+        // copy_nonoverlapping because the source and destination overlap.
+        unsafe {
+            let slot = self.ptr().add(index);
+            ptr::copy(slot, slot.add(1), self.len - index);
+            slot.write(value);
+        }
+        self.len += 1;
+    }
+
+    //Removes and returns the value at `index`, moving everything after it one slot to the left.
+    // Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> T {
+        assert!(index < self.len, "removal index (is {}) should be < len (is {})", index, self.len);
+
+        self.len -= 1;
+        //Safety: index was initialized, it is read out and then the rest is moved over it.
+        unsafe {
+            let slot = self.ptr().add(index);
+            let value = slot.read();
+            ptr::copy(slot.add(1), slot, self.len - index);
+            value
+        }
+    }
+}
+
+impl<T> Default for GrowableArray<T> {
+    fn default() -> GrowableArray<T> {
+        GrowableArray::new()
+    }
+}
+
+impl<T> Drop for GrowableArray<T> {
+    fn drop(&mut self) {
+        //Safety: the first len values are initialized. RawAlloc frees the memory afterwards.
+        unsafe {
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.ptr(), self.len));
+        }
+    }
+}
+
+//Deref to a slice gives GrowableArray all of the slice methods (iter(), len(), indexing, sort(),
+// etc.) for free.
+impl<T> Deref for GrowableArray<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        //Safety: the first len values are initialized.
+        unsafe { slice::from_raw_parts(self.ptr(), self.len) }
+    }
+}
+
+impl<T> DerefMut for GrowableArray<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        //Safety: the first len values are initialized and &mut self makes the access unique.
+        unsafe { slice::from_raw_parts_mut(self.ptr(), self.len) }
+    }
+}
+
+//An owning iterator. It takes over the memory from the GrowableArray and hands the values out
+// one at a time, any that are not taken are dropped along with the iterator.
+pub struct IntoIter<T> {
+    buf: RawAlloc<T>,
+    start: usize,
+    end: usize,
+}
+
+impl<T> IntoIterator for GrowableArray<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        //The array must not run its own Drop because the iterator now owns the values.
+        let array = ManuallyDrop::new(self);
+        //Safety: the buffer is moved out exactly once and the array is never used again.
+        let buf = unsafe { ptr::read(&array.buf) };
+
+        IntoIter {
+            buf,
+            start: 0,
+            end: array.len,
+        }
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.start == self.end {
+            return None;
+        }
+
+        //Safety: start is inside of the initialized range and moving start past it means it will
+        // not be read again.
+        let value = unsafe { self.buf.ptr().add(self.start).read() };
+        self.start += 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.start;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.start == self.end {
+            return None;
+        }
+
+        self.end -= 1;
+        //Safety: the same as next() but from the other end.
+        unsafe { Some(self.buf.ptr().add(self.end).read()) }
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> Drop for IntoIter<T> {
+    fn drop(&mut self) {
+        //Drops the values that were never handed out. RawAlloc frees the memory afterwards.
+        for _ in &mut *self {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    struct DropCounter(Rc<Cell<usize>>);
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn push_and_pop() {
+        let mut array = GrowableArray::new();
+        for i in 0..20 {
+            array.push(i);
+        }
+
+        assert_eq!(array.len(), 20);
+        assert_eq!(array.capacity(), 32);
+        assert_eq!(array.pop(), Some(19));
+        assert_eq!(array.len(), 19);
+        assert_eq!(array[18], 18);
+    }
+
+    #[test]
+    fn pop_from_empty_is_none() {
+        let mut array: GrowableArray<String> = GrowableArray::new();
+        assert_eq!(array.pop(), None);
+    }
+
+    #[test]
+    fn insert_and_remove_shift_values() {
+        let mut array = GrowableArray::new();
+        array.push('a');
+        array.push('c');
+
+        array.insert(1, 'b');
+        array.insert(3, 'd');
+        array.insert(0, '_');
+        assert_eq!(&*array, ['_', 'a', 'b', 'c', 'd']);
+
+        assert_eq!(array.remove(0), '_');
+        assert_eq!(array.remove(3), 'd');
+        assert_eq!(&*array, ['a', 'b', 'c']);
+    }
+
+    #[test]
+    #[should_panic(expected = "insertion index (is 2) should be <= len (is 1)")]
+    fn insert_out_of_bounds_panics() {
+        let mut array = GrowableArray::new();
+        array.push(1);
+        array.insert(2, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "removal index (is 1) should be < len (is 1)")]
+    fn remove_out_of_bounds_panics() {
+        let mut array = GrowableArray::new();
+        array.push(1);
+        array.remove(1);
+    }
+
+    #[test]
+    fn deref_gives_slice_methods() {
+        let mut array = GrowableArray::new();
+        for value in [3, 1, 2] {
+            array.push(value);
+        }
+
+        array.sort();
+        assert_eq!(array.iter().sum::<i32>(), 6);
+        assert_eq!(array.first(), Some(&1));
+    }
+
+    #[test]
+    fn drop_drops_every_value() {
+        let drops = Rc::new(Cell::new(0));
+        let mut array = GrowableArray::new();
+        for _ in 0..10 {
+            array.push(DropCounter(Rc::clone(&drops)));
+        }
+
+        drop(array.remove(4));
+        assert_eq!(drops.get(), 1);
+
+        drop(array);
+        assert_eq!(drops.get(), 10);
+    }
+
+    #[test]
+    fn into_iter_yields_values_in_order() {
+        let mut array = GrowableArray::new();
+        for word in ["a", "b", "c", "d"] {
+            array.push(String::from(word));
+        }
+
+        let mut iter = array.into_iter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next_back().as_deref(), Some("d"));
+        assert_eq!(iter.collect::<Vec<_>>(), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn partially_consumed_iterator_drops_the_rest() {
+        let drops = Rc::new(Cell::new(0));
+        let mut array = GrowableArray::new();
+        for _ in 0..6 {
+            array.push(DropCounter(Rc::clone(&drops)));
+        }
+
+        let mut iter = array.into_iter();
+        let first = iter.next().unwrap();
+        iter.next();
+        assert_eq!(drops.get(), 1);
+
+        drop(iter);
+        assert_eq!(drops.get(), 5);
+
+        drop(first);
+        assert_eq!(drops.get(), 6);
+    }
+
+    #[test]
+    fn zero_sized_values() {
+        let mut array = GrowableArray::new();
+        for _ in 0..1000 {
+            array.push(());
+        }
+        array.insert(10, ());
+
+        assert_eq!(array.len(), 1001);
+        assert_eq!(array.capacity(), usize::MAX);
+        assert_eq!(array.remove(0), ());
+        assert_eq!(array.into_iter().count(), 1000);
+    }
+}
@@ -0,0 +1,108 @@
+//Helpers for looking at values as raw bytes. The standard library already has to_le_bytes() and
+// from_le_bytes() for this, these versions show how the same thing can be done by hand with a
+// union and with raw pointer casts.
+
+use std::mem;
+use std::slice;
+
+//Both fields are 4 bytes long, so writing one and reading the other reinterprets the bytes
+// without any copying or conversion. This is the same thing that mem::transmute() does, but a
+// union makes it clear which two types are involved.
+#[repr(C)]
+union U32Bytes {
+    value: u32,
+    bytes: [u8; 4],
+}
+
+//The union works with bytes in the machine's own order. u32::from_le() and to_le() swap the bytes
+// on big endian machines and do nothing on little endian ones, so the results are always little
+// endian.
+pub fn u32_from_le_bytes_union(bytes: [u8; 4]) -> u32 {
+    //Safety: every possible set of 4 bytes is a valid u32.
+    let native = unsafe { U32Bytes { bytes }.value };
+    u32::from_le(native)
+}
+
+pub fn u32_to_le_bytes_union(value: u32) -> [u8; 4] {
+    //Safety: every u32 is a valid [u8; 4].
+    unsafe { U32Bytes { value: value.to_le() }.bytes }
+}
+
+//The trait that Pod depends on is inside of a private module. Code outside of this file can name
+// Pod but cannot name Sealed, so it has no way of implementing Pod for its own types.
+mod private {
+    pub trait Sealed {}
+}
+
+/// Plain old data: types where every byte is initialized and any bit pattern is valid.
+///
+/// # Safety
+///
+/// Implementing this for a type with padding bytes (such as most structs) or with invalid bit
+/// patterns (such as bool or char) would let bytes_of() read uninitialized memory. That is why
+/// the trait is sealed and only implemented below for the primitive number types.
+pub unsafe trait Pod: private::Sealed + Copy {}
+
+macro_rules! impl_pod {
+    ( $( $t:ty ),* ) => {
+        $(
+            impl private::Sealed for $t {}
+            unsafe impl Pod for $t {}
+        )*
+    };
+}
+
+impl_pod!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+//Views the value as its bytes, in the machine's own order. The returned slice borrows from the
+// value so it cannot outlive it.
+pub fn bytes_of<T: Pod>(value: &T) -> &[u8] {
+    //Safety: a reference is always non-null and aligned, u8 has an alignment of 1, and Pod
+    // guarantees that all size_of::<T>() bytes are initialized.
+    unsafe { slice::from_raw_parts(value as *const T as *const u8, mem::size_of::<T>()) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn union_conversions_match_std() {
+        for value in [0, 1, 0x1234_5678, u32::MAX] {
+            let bytes = u32_to_le_bytes_union(value);
+
+            assert_eq!(bytes, value.to_le_bytes());
+            assert_eq!(u32_from_le_bytes_union(bytes), u32::from_le_bytes(bytes));
+            assert_eq!(u32_from_le_bytes_union(bytes), value);
+        }
+    }
+
+    #[test]
+    fn unaligned_bytes_are_read_correctly() {
+        //Reading 4 bytes starting at an odd offset. A u32 read straight from that address would be
+        // misaligned, the union avoids that because the bytes are copied into it first.
+        let buffer: Vec<u8> = (1..=9).collect();
+        for offset in 0..=5 {
+            let bytes: [u8; 4] = buffer[offset..offset + 4].try_into().unwrap();
+            assert_eq!(u32_from_le_bytes_union(bytes), u32::from_le_bytes(bytes));
+        }
+    }
+
+    #[test]
+    fn bytes_of_matches_to_ne_bytes() {
+        assert_eq!(bytes_of(&0x0102_0304_u32), 0x0102_0304_u32.to_ne_bytes());
+        assert_eq!(bytes_of(&-2_i16), (-2_i16).to_ne_bytes());
+        assert_eq!(bytes_of(&1.5_f64), 1.5_f64.to_ne_bytes());
+        assert_eq!(bytes_of(&7_u8), [7]);
+    }
+
+    #[test]
+    fn bytes_of_points_at_the_value() {
+        let value = 42_u64;
+        let bytes = bytes_of(&value);
+
+        assert_eq!(bytes.as_ptr(), &value as *const u64 as *const u8);
+        assert_eq!(bytes.as_ptr() as usize % mem::align_of::<u64>(), 0);
+        assert_eq!(bytes.len(), 8);
+    }
+}
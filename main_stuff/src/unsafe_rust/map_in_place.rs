@@ -0,0 +1,110 @@
+//Replacing every element of a slice with a function of itself, without cloning and without needing
+// an owned Vec. ptr::read() moves the element out of its slot, which leaves the slot holding a
+// value that has already been moved. ptr::write() then puts the new value in without dropping
+// what was there, since that was already moved out.
+//
+//The tricky part is a panic in `f`. At that point the element it was given is gone, but the slot
+// still looks like it holds a value, and whoever owns the slice will drop it again later. So a
+// guard watches each slot while `f` runs. If `f` panics, the guard fills the slot with
+// T::default() before the panic continues. Elements before it keep their new values and
+// elements after it keep their old ones. That is why T has to implement Default, without some
+// value to put back there is nothing sound to do except abort the process.
+
+use std::ptr;
+
+//Refills one slot with a default value if it is dropped before being disarmed.
+struct RefillOnPanic<T: Default> {
+    slot: *mut T,
+}
+
+impl<T: Default> Drop for RefillOnPanic<T> {
+    fn drop(&mut self) {
+        //Safety: the slot is valid for writes and its old value was moved out by ptr::read(), so
+        // writing without dropping is what is needed here.
+        unsafe {
+            ptr::write(self.slot, T::default());
+        }
+    }
+}
+
+pub fn map_in_place<T: Default, F: FnMut(T) -> T>(slice: &mut [T], mut f: F) {
+    for slot in slice.iter_mut() {
+        let slot: *mut T = slot;
+        //Safety: the slot holds a valid T. From here until the write below it counts as empty,
+        // and the guard makes sure it is filled again even if f panics.
+        let old = unsafe { ptr::read(slot) };
+        let guard = RefillOnPanic { slot };
+        let new = f(old);
+        std::mem::forget(guard);
+        //Safety: the old value was moved out above, so nothing is lost by not dropping it.
+        unsafe {
+            ptr::write(slot, new);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::panic::{self, AssertUnwindSafe};
+
+    thread_local! {
+        static CREATED: Cell<usize> = const { Cell::new(0) };
+        static DROPPED: Cell<usize> = const { Cell::new(0) };
+    }
+
+    struct Tracked(u32);
+
+    impl Tracked {
+        fn new(value: u32) -> Tracked {
+            CREATED.with(|c| c.set(c.get() + 1));
+            Tracked(value)
+        }
+    }
+
+    impl Default for Tracked {
+        fn default() -> Tracked {
+            Tracked::new(0)
+        }
+    }
+
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            DROPPED.with(|d| d.set(d.get() + 1));
+        }
+    }
+
+    #[test]
+    fn maps_every_element() {
+        let mut words = vec![String::from("a"), String::from("b")];
+        map_in_place(&mut words, |mut word| {
+            word.push('!');
+            word
+        });
+
+        assert_eq!(words, ["a!", "b!"]);
+    }
+
+    #[test]
+    fn panic_midway_refills_the_slot() {
+        let mut values: Vec<Tracked> = (1..=5).map(Tracked::new).collect();
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            map_in_place(&mut values, |t| {
+                if t.0 == 3 {
+                    panic!("cannot map 3");
+                }
+                Tracked::new(t.0 * 10)
+            });
+        }));
+        assert!(result.is_err());
+        let numbers: Vec<u32> = values.iter().map(|t| t.0).collect();
+        assert_eq!(numbers, [10, 20, 0, 4, 5]);
+
+        //Every value that was made has been dropped once, none were dropped twice or leaked.
+        drop(values);
+        assert_eq!(CREATED.with(Cell::get), DROPPED.with(Cell::get));
+        assert_eq!(CREATED.with(Cell::get), 5 + 2 + 1);
+    }
+}
@@ -0,0 +1,171 @@
+//A value that is set up the first time it is needed, which can be put in a static. Doing this with
+// a `static mut` and an `if not initialized yet` check is a data race, two threads can both see
+// it as uninitialized and both write to it. OnceInit uses an atomic state so that exactly one
+// thread runs the initializer and every other thread waits for it to finish.
+//
+//The state moves UNINIT -> RUNNING -> READY. The value is only written by the thread that moved
+// the state to RUNNING, and only read once the state is READY, which is never left again.
+//If the initializer panics the state goes back to UNINIT, so the next call tries again with its
+// own closure. Calling get_or_init() on the same OnceInit from inside of its initializer spins
+// forever, since it waits for itself to finish.
+
+use std::cell::UnsafeCell;
+use std::mem::MaybeUninit;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::thread;
+
+const UNINIT: u8 = 0;
+const RUNNING: u8 = 1;
+const READY: u8 = 2;
+
+pub struct OnceInit<T> {
+    state: AtomicU8,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+//Safety: the value is written once by a single thread before anyone can read it, and after that
+// it is only ever read. It can be created on one thread and dropped on another, so T also needs
+// to be Send.
+unsafe impl<T: Send + Sync> Sync for OnceInit<T> {}
+unsafe impl<T: Send> Send for OnceInit<T> {}
+
+//Puts the state back to UNINIT if the initializer panics. It is forgotten once the value is set.
+struct ResetOnPanic<'a>(&'a AtomicU8);
+
+impl Drop for ResetOnPanic<'_> {
+    fn drop(&mut self) {
+        self.0.store(UNINIT, Ordering::Release);
+    }
+}
+
+impl<T> OnceInit<T> {
+    pub const fn new() -> OnceInit<T> {
+        OnceInit {
+            state: AtomicU8::new(UNINIT),
+            value: UnsafeCell::new(MaybeUninit::uninit()),
+        }
+    }
+
+    pub fn get(&self) -> Option<&T> {
+        if self.state.load(Ordering::Acquire) == READY {
+            //Safety: READY means the value has been written, and it is never written again.
+            Some(unsafe { (*self.value.get()).assume_init_ref() })
+        } else {
+            None
+        }
+    }
+
+    pub fn get_or_init(&self, f: impl FnOnce() -> T) -> &T {
+        let mut f = Some(f);
+        loop {
+            let claimed =
+                self.state.compare_exchange(UNINIT, RUNNING, Ordering::Acquire, Ordering::Acquire);
+            match claimed {
+                Ok(_) => {
+                    let reset = ResetOnPanic(&self.state);
+                    let value = (f.take().expect("only one thread gets to initialize"))();
+                    //Safety: this thread moved the state to RUNNING, so nothing else is reading
+                    // or writing the value.
+                    unsafe {
+                        (*self.value.get()).write(value);
+                    }
+                    std::mem::forget(reset);
+                    //Release makes the write above visible to any thread that then sees READY.
+                    self.state.store(READY, Ordering::Release);
+                }
+                Err(READY) => {}
+                //Another thread is running its initializer, wait for it to finish or panic.
+                Err(_) => {
+                    thread::yield_now();
+                    continue;
+                }
+            }
+
+            return self.get().expect("the state is READY");
+        }
+    }
+}
+
+impl<T> Default for OnceInit<T> {
+    fn default() -> OnceInit<T> {
+        OnceInit::new()
+    }
+}
+
+impl<T> Drop for OnceInit<T> {
+    fn drop(&mut self) {
+        if *self.state.get_mut() == READY {
+            //Safety: READY means the value was written, and it is not used after this.
+            unsafe {
+                self.value.get_mut().assume_init_drop();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic;
+    use std::rc::Rc;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::Barrier;
+
+    #[test]
+    fn initializes_exactly_once_across_threads() {
+        static VALUE: OnceInit<String> = OnceInit::new();
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        let barrier = Barrier::new(8);
+
+        thread::scope(|scope| {
+            for i in 0..8 {
+                let barrier = &barrier;
+                scope.spawn(move || {
+                    barrier.wait();
+                    let value = VALUE.get_or_init(|| {
+                        CALLS.fetch_add(1, Ordering::SeqCst);
+                        format!("set by thread {}", i)
+                    });
+                    assert!(value.starts_with("set by thread"));
+                });
+            }
+        });
+
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+        assert!(VALUE.get().is_some());
+    }
+
+    #[test]
+    fn get_before_and_after_init() {
+        let once = OnceInit::new();
+
+        assert_eq!(once.get(), None);
+        assert_eq!(once.get_or_init(|| 5), &5);
+        assert_eq!(once.get_or_init(|| 6), &5);
+        assert_eq!(once.get(), Some(&5));
+    }
+
+    #[test]
+    fn panicking_initializer_allows_a_retry() {
+        let once = OnceInit::new();
+
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            once.get_or_init(|| -> i32 { panic!("initializer failed") });
+        }));
+        assert!(result.is_err());
+        assert_eq!(once.get(), None);
+
+        assert_eq!(once.get_or_init(|| 7), &7);
+    }
+
+    #[test]
+    fn drops_the_value() {
+        let shared = Rc::new(());
+        {
+            let once = OnceInit::new();
+            once.get_or_init(|| Rc::clone(&shared));
+            assert_eq!(Rc::strong_count(&shared), 2);
+        }
+        assert_eq!(Rc::strong_count(&shared), 1);
+    }
+}
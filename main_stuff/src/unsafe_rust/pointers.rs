@@ -0,0 +1,527 @@
+//As a side note, in order to get around the rules of the Rust borrow checker generally raw
+// pointers are used. The rules that Rust uses only seem to apply to its smart pointers. So the
+// below code will work even though there are two different mutable references to the same
+// variable.
+//This is the same idea as the standard library split_at_mut(). The borrow checker cannot tell
+// that the two halves do not overlap, but by checking `mid` first the unsafe block is always
+// given valid lengths, so the function as a whole is safe to call.
+
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::marker::PhantomData;
+use std::ops::Range;
+use std::ptr::NonNull;
+use std::slice;
+
+//Splits the slice into `[0, mid)` and `[mid, len)`. Panics if `mid` is greater than the length.
+pub fn split_at_mut<T>(slice: &mut [T], mid: usize) -> (&mut [T], &mut [T]) {
+    match try_split_at_mut(slice, mid) {
+        Ok(halves) => halves,
+        Err(error) => panic!("{}", error),
+    }
+}
+
+//Returned by try_split_at_mut() when `mid` is past the end of the slice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SplitError {
+    pub len: usize,
+    pub mid: usize,
+}
+
+impl Display for SplitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "mid ({}) is greater than the length of the slice ({})", self.mid, self.len)
+    }
+}
+
+impl std::error::Error for SplitError {}
+
+//The same as split_at_mut() except that it returns an error instead of panicking.
+pub fn try_split_at_mut<T>(slice: &mut [T], mid: usize) -> Result<(&mut [T], &mut [T]), SplitError> {
+    let len = slice.len();
+    if mid > len {
+        return Err(SplitError { len, mid });
+    }
+
+    //Safety: mid <= len was checked above, and the pointer comes from a slice that stays mutably
+    // borrowed for as long as the halves are alive.
+    unsafe { Ok(split_raw(slice.as_mut_ptr(), len, mid)) }
+}
+
+/// Builds the two halves `[0, mid)` and `[mid, len)` out of a pointer to the whole slice.
+///
+/// # Safety
+///
+/// `ptr` must be valid for reads and writes of `len` values of T for all of `'a`, nothing else
+/// may use that memory while the halves are alive, and `mid` must be at most `len`.
+unsafe fn split_raw<'a, T>(ptr: *mut T, len: usize, mid: usize) -> (&'a mut [T], &'a mut [T]) {
+    //Safety: both halves are inside of the memory the caller vouched for, and they do not
+    // overlap so handing out two mutable slices is fine.
+    unsafe {
+        //These variable are mutable, the pointers themselves are not mutable.
+        (
+            slice::from_raw_parts_mut(ptr, mid),
+            slice::from_raw_parts_mut(ptr.add(mid), len - mid),
+        )
+    }
+}
+
+//Splits the slice into `n` pieces of `len / n` elements each, except the last piece which also
+// gets whatever is left over. So 10 elements in 3 pieces are 3, 3 and 4 long. Panics if `n` is 0.
+pub fn split_at_mut_n<T>(slice: &mut [T], n: usize) -> Vec<&mut [T]> {
+    assert!(n > 0, "cannot split a slice into 0 pieces");
+
+    let len = slice.len();
+    let size = len / n;
+    let ptr = slice.as_mut_ptr();
+
+    //Safety: piece i starts at i * size and the last one ends at len, so every piece is inside of
+    // the slice and none of them overlap. The slice stays mutably borrowed while they are alive.
+    unsafe {
+        (0..n)
+            .map(|i| {
+                let start = i * size;
+                let end = if i == n - 1 { len } else { start + size };
+                slice::from_raw_parts_mut(ptr.add(start), end - start)
+            })
+            .collect()
+    }
+}
+
+//The two halves from try_split_at_mut() kept together with names, instead of as a bare tuple.
+// Because they always come from splitting one slice, they can be joined back into it again.
+//
+//Only a pointer to the whole slice is kept, and the halves are made from it when they are asked
+// for. recombine() needs a pointer that is allowed to reach every element, and one taken from
+// the left half is only allowed to reach the left half.
+pub struct SafeSlicePair<'a, T> {
+    whole: NonNull<T>,
+    len: usize,
+    mid: usize,
+    //The pair acts like the &'a mut [T] that it was split from.
+    _marker: PhantomData<&'a mut [T]>,
+}
+
+//Safety: the pair only hands out the same access as the &mut [T] it was made from, so it can be
+// sent and shared whenever that can.
+unsafe impl<T: Send> Send for SafeSlicePair<'_, T> {}
+unsafe impl<T: Sync> Sync for SafeSlicePair<'_, T> {}
+
+impl<'a, T> SafeSlicePair<'a, T> {
+    pub fn split(slice: &'a mut [T], mid: usize) -> Result<SafeSlicePair<'a, T>, SplitError> {
+        let len = slice.len();
+        if mid > len {
+            return Err(SplitError { len, mid });
+        }
+
+        Ok(SafeSlicePair {
+            whole: NonNull::from(slice).cast(),
+            len,
+            mid,
+            _marker: PhantomData,
+        })
+    }
+
+    pub fn left(&self) -> &[T] {
+        //Safety: the first mid values are inside of the slice, and &self means nothing is
+        // changing them.
+        unsafe { slice::from_raw_parts(self.whole.as_ptr(), self.mid) }
+    }
+
+    pub fn right(&self) -> &[T] {
+        //Safety: the same as left() for the values after mid.
+        unsafe { slice::from_raw_parts(self.whole.as_ptr().add(self.mid), self.len - self.mid) }
+    }
+
+    pub fn left_mut(&mut self) -> &mut [T] {
+        self.both_mut().0
+    }
+
+    pub fn right_mut(&mut self) -> &mut [T] {
+        self.both_mut().1
+    }
+
+    pub fn both_mut(&mut self) -> (&mut [T], &mut [T]) {
+        //Safety: mid <= len was checked by split(), and &mut self makes the access unique for as
+        // long as the halves are alive.
+        unsafe { split_raw(self.whole.as_ptr(), self.len, self.mid) }
+    }
+
+    //Gives back the original slice.
+    pub fn recombine(self) -> &'a mut [T] {
+        //Safety: whole and len are the slice that split() was given, which is still borrowed for
+        // 'a. The pair is consumed, so no half can be used again.
+        unsafe { slice::from_raw_parts_mut(self.whole.as_ptr(), self.len) }
+    }
+}
+
+//Why disjoint_slices_mut() could not hand out the two slices.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OverlapError {
+    //The range is backwards or runs past the end of the slice.
+    OutOfBounds { range: Range<usize>, len: usize },
+    //Both ranges include the elements in `overlap`.
+    Overlapping { overlap: Range<usize> },
+}
+
+impl Display for OverlapError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            OverlapError::OutOfBounds { range, len } => {
+                write!(f, "range {:?} is out of bounds for a slice of length {}", range, len)
+            }
+            OverlapError::Overlapping { overlap } => {
+                write!(f, "the ranges overlap at {:?}", overlap)
+            }
+        }
+    }
+}
+
+impl std::error::Error for OverlapError {}
+
+//The general version of split_at_mut(), any two ranges of the slice can be borrowed mutably at the
+// same time as long as they do not share an element. The slices come back in the same order as
+// the ranges, so `b` can come before `a` in the slice.
+pub fn disjoint_slices_mut<T>(
+    slice: &mut [T],
+    a: Range<usize>,
+    b: Range<usize>,
+) -> Result<(&mut [T], &mut [T]), OverlapError> {
+    let len = slice.len();
+    for range in [&a, &b] {
+        if range.start > range.end || range.end > len {
+            return Err(OverlapError::OutOfBounds { range: range.clone(), len });
+        }
+    }
+
+    //Two ranges share an element when each one starts before the other one ends. An empty range
+    // has no elements, so it never overlaps anything.
+    let overlap = a.start.max(b.start)..a.end.min(b.end);
+    if !overlap.is_empty() {
+        return Err(OverlapError::Overlapping { overlap });
+    }
+
+    let ptr = slice.as_mut_ptr();
+    //Safety: both ranges were checked to be inside of the slice and to not share any elements,
+    // so the two mutable slices never alias. The original slice stays borrowed while they live.
+    unsafe {
+        Ok((
+            slice::from_raw_parts_mut(ptr.add(a.start), a.len()),
+            slice::from_raw_parts_mut(ptr.add(b.start), b.len()),
+        ))
+    }
+}
+
+//Swaps the first half of the slice with the second half, element by element, so [1, 2, 3, 4]
+// becomes [3, 4, 1, 2]. When the length is odd the middle element stays where it is, so
+// [1, 2, 3, 4, 5] becomes [4, 5, 3, 1, 2]. The two halves are borrowed through split_at_mut(),
+// which is what allows swapping between them without any unsafe code here.
+pub fn swap_halves<T>(slice: &mut [T]) {
+    let half = slice.len() / 2;
+    let (left, rest) = split_at_mut(slice, half);
+    let start = rest.len() - half;
+    let right = &mut rest[start..];
+
+    for (a, b) in left.iter_mut().zip(right.iter_mut()) {
+        std::mem::swap(a, b);
+    }
+}
+
+//Why guarded_deref() would not turn a pointer into a reference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PtrError {
+    Null,
+    Misaligned { address: usize, align: usize },
+}
+
+impl Display for PtrError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PtrError::Null => write!(f, "the pointer is null"),
+            PtrError::Misaligned { address, align } => {
+                write!(f, "address {:#x} is not a multiple of the alignment {}", address, align)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PtrError {}
+
+/// Turns a raw pointer into a reference, returning an error for a null or misaligned pointer
+/// instead of the undefined behavior dereferencing one would be.
+///
+/// Those are the only two mistakes that can be seen from the pointer itself. There is no way to
+/// ask whether an address points at a live T, so a pointer to freed memory or into the middle of
+/// some other value looks just as good as a real one.
+///
+/// # Safety
+///
+/// If the pointer is non-null and aligned, it must point to a valid, initialized T that is not
+/// changed or freed for as long as the returned reference is used.
+pub unsafe fn guarded_deref<'a, T>(ptr: *const T) -> Result<&'a T, PtrError> {
+    if ptr.is_null() {
+        return Err(PtrError::Null);
+    }
+    if !ptr.is_aligned() {
+        return Err(PtrError::Misaligned {
+            address: ptr.addr(),
+            align: std::mem::align_of::<T>(),
+        });
+    }
+
+    //Safety: the pointer was checked for null and alignment above, the caller promises the rest.
+    Ok(unsafe { &*ptr })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guarded_deref_of_a_local() {
+        let value = 42_u64;
+
+        //Safety: the pointer comes from a live local that outlives the reference.
+        let reference = unsafe { guarded_deref(&value as *const u64) };
+        assert_eq!(reference, Ok(&42));
+    }
+
+    #[test]
+    fn guarded_deref_rejects_null() {
+        //Safety: a null pointer is rejected before anything is read.
+        let result = unsafe { guarded_deref(std::ptr::null::<u32>()) };
+
+        assert_eq!(result, Err(PtrError::Null));
+        assert_eq!(PtrError::Null.to_string(), "the pointer is null");
+    }
+
+    #[test]
+    fn guarded_deref_rejects_misaligned() {
+        let values = [0_u32; 2];
+        //One byte past the start of a u32 can never be aligned for another u32.
+        let misaligned = values.as_ptr().cast::<u8>().wrapping_add(1).cast::<u32>();
+
+        //Safety: a misaligned pointer is rejected before anything is read.
+        let result = unsafe { guarded_deref(misaligned) };
+
+        assert_eq!(
+            result,
+            Err(PtrError::Misaligned { address: misaligned.addr(), align: 4 })
+        );
+    }
+
+    #[test]
+    fn split_at_zero() {
+        let mut values = [1, 2, 3];
+        let (left, right) = split_at_mut(&mut values, 0);
+
+        assert!(left.is_empty());
+        assert_eq!(right, [1, 2, 3]);
+    }
+
+    #[test]
+    fn split_at_len() {
+        let mut values = [1, 2, 3];
+        let (left, right) = split_at_mut(&mut values, 3);
+
+        assert_eq!(left, [1, 2, 3]);
+        assert!(right.is_empty());
+    }
+
+    #[test]
+    fn split_in_the_middle() {
+        let mut values = [1, 2, 3, 4, 5];
+        let (left, right) = split_at_mut(&mut values, 3);
+
+        assert_eq!(left, [1, 2, 3]);
+        assert_eq!(right, [4, 5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "mid (4) is greater than the length of the slice")]
+    fn split_beyond_len_panics() {
+        let mut values = [1, 2, 3];
+        split_at_mut(&mut values, 4);
+    }
+
+    #[test]
+    fn try_split_in_bounds_is_ok() {
+        let mut values = [1, 2, 3];
+        let (left, right) = try_split_at_mut(&mut values, 1).unwrap();
+
+        assert_eq!(left, [1]);
+        assert_eq!(right, [2, 3]);
+        assert!(try_split_at_mut(&mut values, 3).is_ok());
+    }
+
+    #[test]
+    fn try_split_beyond_len_is_an_error() {
+        let mut values = [1, 2, 3];
+        let error = try_split_at_mut(&mut values, 4).unwrap_err();
+
+        assert_eq!(error, SplitError { len: 3, mid: 4 });
+        assert_eq!(error.to_string(), "mid (4) is greater than the length of the slice (3)");
+    }
+
+    #[test]
+    fn split_n_even() {
+        let mut values = [1, 2, 3, 4, 5, 6];
+        let pieces = split_at_mut_n(&mut values, 3);
+
+        assert_eq!(pieces, [&mut [1, 2][..], &mut [3, 4][..], &mut [5, 6][..]]);
+    }
+
+    #[test]
+    fn split_n_remainder_goes_last() {
+        let mut values: Vec<u32> = (0..10).collect();
+        let mut pieces = split_at_mut_n(&mut values, 3);
+
+        let lengths: Vec<usize> = pieces.iter().map(|piece| piece.len()).collect();
+        assert_eq!(lengths, [3, 3, 4]);
+
+        for (i, piece) in pieces.iter_mut().enumerate() {
+            piece[0] = 100 + i as u32;
+        }
+        assert_eq!(values, [100, 1, 2, 101, 4, 5, 102, 7, 8, 9]);
+    }
+
+    #[test]
+    fn split_n_equal_to_len() {
+        let mut values = ['a', 'b', 'c'];
+        let pieces = split_at_mut_n(&mut values, 3);
+
+        assert!(pieces.iter().all(|piece| piece.len() == 1));
+        assert_eq!(split_at_mut_n(&mut [0; 0], 2).len(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot split a slice into 0 pieces")]
+    fn split_n_zero_panics() {
+        split_at_mut_n(&mut [1, 2, 3], 0);
+    }
+
+    #[test]
+    fn slice_pair_mutates_both_halves() {
+        let mut values = [1, 2, 3, 4, 5];
+        let mut pair = SafeSlicePair::split(&mut values, 2).unwrap();
+
+        pair.left_mut()[0] = 10;
+        pair.right_mut()[2] = 50;
+        let (left, right) = pair.both_mut();
+        std::mem::swap(&mut left[1], &mut right[0]);
+
+        assert_eq!(pair.left(), [10, 3]);
+        assert_eq!(pair.right(), [2, 4, 50]);
+        assert_eq!(values, [10, 3, 2, 4, 50]);
+    }
+
+    #[test]
+    fn slice_pair_recombines() {
+        let mut values = vec![String::from("a"), String::from("b"), String::from("c")];
+        let mut pair = SafeSlicePair::split(&mut values, 1).unwrap();
+        pair.right_mut()[0].push('!');
+
+        let whole = pair.recombine();
+        whole[2].push('?');
+        assert_eq!(whole, ["a", "b!", "c?"]);
+        assert_eq!(values, ["a", "b!", "c?"]);
+    }
+
+    #[test]
+    fn slice_pair_rejects_a_bad_mid() {
+        let mut values = [1, 2];
+
+        assert_eq!(
+            SafeSlicePair::split(&mut values, 3).err(),
+            Some(SplitError { len: 2, mid: 3 })
+        );
+    }
+
+    #[test]
+    fn disjoint_adjacent_ranges_are_allowed() {
+        let mut values = [1, 2, 3, 4, 5];
+        let (a, b) = disjoint_slices_mut(&mut values, 2..5, 0..2).unwrap();
+        a[0] = 30;
+        b[1] = 20;
+
+        assert_eq!(values, [1, 20, 30, 4, 5]);
+    }
+
+    #[test]
+    fn disjoint_identical_ranges_overlap() {
+        let mut values = [0; 4];
+
+        assert_eq!(
+            disjoint_slices_mut(&mut values, 1..3, 1..3),
+            Err(OverlapError::Overlapping { overlap: 1..3 })
+        );
+    }
+
+    #[test]
+    fn disjoint_partial_overlaps() {
+        let mut values = [0; 6];
+
+        assert_eq!(
+            disjoint_slices_mut(&mut values, 0..3, 2..5),
+            Err(OverlapError::Overlapping { overlap: 2..3 })
+        );
+        assert_eq!(
+            disjoint_slices_mut(&mut values, 3..6, 1..4),
+            Err(OverlapError::Overlapping { overlap: 3..4 })
+        );
+        assert!(disjoint_slices_mut(&mut values, 2..2, 0..6).is_ok());
+    }
+
+    #[test]
+    fn disjoint_out_of_bounds() {
+        let mut values = [0; 3];
+
+        assert_eq!(
+            disjoint_slices_mut(&mut values, 0..1, 2..4),
+            Err(OverlapError::OutOfBounds { range: 2..4, len: 3 })
+        );
+        let error = disjoint_slices_mut(&mut values, 0..5, 0..0).unwrap_err();
+        assert_eq!(error.to_string(), "range 0..5 is out of bounds for a slice of length 3");
+    }
+
+    #[test]
+    fn swap_halves_even_length() {
+        let mut values = [1, 2, 3, 4, 5, 6];
+        swap_halves(&mut values);
+
+        assert_eq!(values, [4, 5, 6, 1, 2, 3]);
+    }
+
+    #[test]
+    fn swap_halves_odd_length_keeps_the_middle() {
+        let mut values = [1, 2, 3, 4, 5];
+        swap_halves(&mut values);
+
+        assert_eq!(values, [4, 5, 3, 1, 2]);
+    }
+
+    #[test]
+    fn swap_halves_empty_and_single() {
+        let mut empty: [String; 0] = [];
+        swap_halves(&mut empty);
+
+        let mut single = [String::from("only")];
+        swap_halves(&mut single);
+        assert_eq!(single, ["only"]);
+    }
+
+    #[test]
+    fn halves_are_disjoint() {
+        let mut values = vec![0; 6];
+        let (left, right) = split_at_mut(&mut values, 2);
+
+        for value in left.iter_mut() {
+            *value = 1;
+        }
+        for value in right.iter_mut() {
+            *value = 2;
+        }
+
+        assert_eq!(values, [1, 1, 2, 2, 2, 2]);
+    }
+}
@@ -0,0 +1,194 @@
+//Some `C` functions take a function pointer and call back into the program, qsort() is the classic
+// example. The comparator has to be an `extern "C" fn` so that `C` knows how to call it, and it
+// only ever gets untyped `void` pointers to the two elements.
+//
+//qsort() only moves elements around as plain bytes, which is exactly what a move is in Rust, so
+// it is fine to sort any Rust type with it. What it does not promise is anything at all when the
+// comparator is inconsistent, some versions of glibc read and write outside of the array when
+// that happens. Rust's own sort has to stay safe even with a broken Ord, so the functions that
+// take an arbitrary comparator are unsafe and the caller promises it is a total order.
+
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::mem;
+use std::os::raw::{c_int, c_void};
+use std::panic::{self, AssertUnwindSafe};
+use std::process;
+use std::ptr;
+
+type Compare = extern "C" fn(*const c_void, *const c_void) -> c_int;
+
+extern "C" {
+    fn qsort(base: *mut c_void, count: usize, size: usize, compare: Compare);
+}
+
+//The comparator for an i32 slice. `C` guarantees both pointers point into the array being sorted.
+extern "C" fn compare_i32(a: *const c_void, b: *const c_void) -> c_int {
+    //Safety: qsort() only ever passes pointers to elements of the i32 slice.
+    let (a, b) = unsafe { (*(a as *const i32), *(b as *const i32)) };
+    a.cmp(&b) as c_int
+}
+
+pub fn sort_i32_via_qsort(data: &mut [i32]) {
+    //Safety: the pointer, length and element size all describe the slice, and compare_i32 is the
+    // right comparator for i32 elements.
+    unsafe {
+        qsort(data.as_mut_ptr() as *mut c_void, data.len(), mem::size_of::<i32>(), compare_i32);
+    }
+}
+
+//A plain function pointer cannot carry a closure along with it, and qsort() has no spare argument
+// to pass one through. So the closure is parked in a thread_local while qsort() runs and a single
+// trampoline function looks it up. Each thread has its own slot, so sorts on different threads
+// cannot see each other's closures.
+type Comparator<'a> = dyn FnMut(*const c_void, *const c_void) -> Ordering + 'a;
+
+thread_local! {
+    //Points at a `&mut Comparator` that lives on the stack of sort_by_with_qsort().
+    static COMPARATOR: Cell<*mut c_void> = const { Cell::new(ptr::null_mut()) };
+}
+
+extern "C" fn trampoline(a: *const c_void, b: *const c_void) -> c_int {
+    let comparator = COMPARATOR.with(Cell::get) as *mut &mut Comparator;
+    //Safety: the trampoline is only handed to qsort() by sort_by_with_qsort(), which sets the
+    // pointer before calling qsort() and keeps the closure alive until it returns.
+    let result = panic::catch_unwind(AssertUnwindSafe(|| unsafe { (*comparator)(a, b) }));
+
+    //A panic cannot unwind through qsort(). It cannot be stored and resumed later either, since
+    // qsort() would still need answers for the rest of its comparisons and any made up answer
+    // could contradict the ones it already got.
+    match result {
+        Ok(ordering) => ordering as c_int,
+        Err(_) => {
+            eprintln!("the qsort() comparator panicked, aborting");
+            process::abort()
+        }
+    }
+}
+
+/// Sorts the slice with the `C` qsort(), comparing elements with a Rust closure. If the closure
+/// panics the process is aborted, since the panic cannot unwind through qsort().
+///
+/// # Safety
+///
+/// `compare` must be a total order: for the same two elements it always gives the same answer,
+/// `compare(a, b)` is the reverse of `compare(b, a)`, and if `a < b` and `b < c` then `a < c`.
+/// qsort() may read and write outside of the slice when that does not hold.
+pub unsafe fn sort_by_with_qsort<T, F>(data: &mut [T], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    //Zero sized values are all the same, so there is nothing to sort.
+    if data.len() < 2 || mem::size_of::<T>() == 0 {
+        return;
+    }
+
+    let mut erased = |a: *const c_void, b: *const c_void| {
+        //Safety: qsort() only ever passes pointers to elements of `data`, which are valid Ts.
+        unsafe { compare(&*(a as *const T), &*(b as *const T)) }
+    };
+    let mut comparator: &mut Comparator = &mut erased;
+
+    //The previous value is put back afterwards in case this sort is running inside of the
+    // comparator of another one.
+    let erased_ptr = &mut comparator as *mut &mut Comparator as *mut c_void;
+    let previous = COMPARATOR.with(|c| c.replace(erased_ptr));
+    //Safety: the pointer, length and element size all describe the slice, the trampoline finds
+    // the comparator for T through the thread_local that was just set, and the caller promised
+    // that the comparator is a total order.
+    unsafe {
+        qsort(data.as_mut_ptr() as *mut c_void, data.len(), mem::size_of::<T>(), trampoline);
+    }
+    COMPARATOR.with(|c| c.set(previous));
+}
+
+/// Sorts the slice with the `C` qsort() using the Ord implementation of T.
+///
+/// # Safety
+///
+/// The Ord implementation of T must be a total order, see sort_by_with_qsort(). Ord is a safe
+/// trait, so a wrong implementation is allowed to exist. Every Ord in the standard library is a
+/// total order.
+pub unsafe fn sort_with_qsort<T: Ord>(data: &mut [T]) {
+    //Safety: the caller promises that T::cmp is a total order.
+    unsafe { sort_by_with_qsort(data, T::cmp) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //A tiny xorshift generator, good enough for making test data without pulling in a crate.
+    fn random_vec(seed: u64, len: usize) -> Vec<i32> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state as i32
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sort_i32_matches_slice_sort() {
+        for (seed, len) in [(1, 0), (2, 1), (3, 2), (4, 100), (5, 1000)] {
+            let mut data = random_vec(seed, len);
+            let mut expected = data.clone();
+            expected.sort();
+
+            sort_i32_via_qsort(&mut data);
+            assert_eq!(data, expected);
+        }
+    }
+
+    #[test]
+    fn sort_with_qsort_matches_slice_sort() {
+        for (seed, len) in [(6, 0), (7, 1), (8, 500)] {
+            let mut data: Vec<String> =
+                random_vec(seed, len).iter().map(|n| n.to_string()).collect();
+            let mut expected = data.clone();
+            expected.sort();
+
+            //Safety: String's Ord is a total order.
+            unsafe { sort_with_qsort(&mut data) };
+            assert_eq!(data, expected);
+        }
+    }
+
+    #[test]
+    fn sort_by_uses_the_closure() {
+        let mut data = random_vec(9, 200);
+        let mut calls = 0;
+        let mut expected = data.clone();
+        expected.sort_by(|a, b| b.cmp(a));
+
+        //Safety: reversing a total order gives another total order.
+        unsafe {
+            sort_by_with_qsort(&mut data, |a, b| {
+                calls += 1;
+                b.cmp(a)
+            });
+        }
+        assert_eq!(data, expected);
+        assert!(calls > 0);
+    }
+
+    #[test]
+    fn nested_sorts_use_their_own_closures() {
+        let mut outer = vec![vec![3, 1, 2], vec![1], vec![9, 8]];
+
+        //Safety: comparing the sorted copies is Vec<i32>'s Ord, a total order, and i32's Ord is one
+        // too.
+        unsafe {
+            sort_by_with_qsort(&mut outer, |a, b| {
+                let (mut a, mut b) = (a.clone(), b.clone());
+                sort_with_qsort(&mut a);
+                sort_with_qsort(&mut b);
+                a.cmp(&b)
+            });
+        }
+        assert_eq!(outer, [vec![1], vec![3, 1, 2], vec![9, 8]]);
+    }
+}
@@ -0,0 +1,196 @@
+//A singly linked list where the links are raw pointers instead of Option<Box<Node<T>>>. Each node
+// is put on the heap with Box::new() and turned into a raw pointer with Box::into_raw(), which
+// hands ownership over to the list. Box::from_raw() takes it back when the node is removed.
+//
+//The invariants that the unsafe code relies on are:
+// 1) `head` is either null or came from Box::into_raw() and has not been freed.
+// 2) The same is true of every `next` pointer, and following them always ends at null.
+// 3) `len` is the number of nodes reachable from `head`.
+//All of the fields are private, so only the code in this file can break these.
+
+use std::marker::PhantomData;
+use std::ptr;
+
+struct Node<T> {
+    value: T,
+    next: *mut Node<T>,
+}
+
+pub struct RawList<T> {
+    head: *mut Node<T>,
+    len: usize,
+    //The list owns its values even though it only holds raw pointers to them.
+    _marker: PhantomData<Box<Node<T>>>,
+}
+
+//Safety: the list owns its nodes just like a Vec owns its elements.
+unsafe impl<T: Send> Send for RawList<T> {}
+unsafe impl<T: Sync> Sync for RawList<T> {}
+
+impl<T> RawList<T> {
+    pub fn new() -> RawList<T> {
+        RawList {
+            head: ptr::null_mut(),
+            len: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.is_null()
+    }
+
+    pub fn push_front(&mut self, value: T) {
+        let node = Box::new(Node {
+            value,
+            next: self.head,
+        });
+        self.head = Box::into_raw(node);
+        self.len += 1;
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.head.is_null() {
+            return None;
+        }
+
+        //Safety: head is not null, so it came from Box::into_raw() (invariant 1). It is unlinked
+        // right away so it can never be freed twice.
+        let node = unsafe { Box::from_raw(self.head) };
+        self.head = node.next;
+        self.len -= 1;
+        Some(node.value)
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        //Safety: a non-null head points to a live node, and the borrow of self keeps it alive.
+        unsafe { self.head.as_ref().map(|node| &node.value) }
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        //Safety: the same as peek(), and the mutable borrow of self makes this the only reference.
+        unsafe { self.head.as_mut().map(|node| &mut node.value) }
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for RawList<T> {
+    fn default() -> RawList<T> {
+        RawList::new()
+    }
+}
+
+//Dropping the nodes one at a time in a loop. Letting each node drop the next one would recurse
+// once per node, which overflows the stack for a long enough list.
+impl<T> Drop for RawList<T> {
+    fn drop(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+}
+
+pub struct Iter<'a, T> {
+    next: *const Node<T>,
+    //The iterator borrows from the list, so the list cannot be changed while it is in use.
+    _marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        //Safety: next is null or a live node of a list that is borrowed for 'a (invariant 2).
+        unsafe {
+            self.next.as_ref().map(|node| {
+                self.next = node.next;
+                &node.value
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    struct DropCounter<'a>(&'a Cell<usize>);
+
+    impl Drop for DropCounter<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn push_and_pop_are_last_in_first_out() {
+        let mut list = RawList::new();
+        list.push_front(1);
+        list.push_front(2);
+        list.push_front(3);
+
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.peek(), Some(&3));
+        assert_eq!(list.pop_front(), Some(3));
+        assert_eq!(list.pop_front(), Some(2));
+        assert_eq!(list.pop_front(), Some(1));
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn pop_from_an_empty_list() {
+        let mut list: RawList<String> = RawList::new();
+
+        assert_eq!(list.pop_front(), None);
+        assert_eq!(list.peek(), None);
+        assert_eq!(list.len(), 0);
+    }
+
+    #[test]
+    fn iter_goes_front_to_back() {
+        let mut list = RawList::new();
+        for word in ["c", "b", "a"] {
+            list.push_front(word.to_string());
+        }
+        *list.peek_mut().unwrap() += "!";
+
+        let items: Vec<&String> = list.iter().collect();
+        assert_eq!(items, ["a!", "b", "c"]);
+    }
+
+    #[test]
+    fn drop_frees_every_node() {
+        let drops = Cell::new(0);
+        {
+            let mut list = RawList::new();
+            for _ in 0..5 {
+                list.push_front(DropCounter(&drops));
+            }
+            drop(list.pop_front());
+            assert_eq!(drops.get(), 1);
+        }
+        assert_eq!(drops.get(), 5);
+    }
+
+    #[test]
+    fn long_list_drops_without_recursing() {
+        //Miri runs far too slowly for the full length, so it only checks that the nodes are freed
+        // correctly. The native run is the one that would overflow the stack.
+        const LEN: usize = if cfg!(miri) { 2_000 } else { 200_000 };
+
+        let mut list = RawList::new();
+        for i in 0..LEN {
+            list.push_front(i);
+        }
+        assert_eq!(list.len(), LEN);
+    }
+}
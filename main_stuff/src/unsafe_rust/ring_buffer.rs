@@ -0,0 +1,211 @@
+//A queue with a fixed capacity where the storage wraps around. `head` is the slot of the oldest
+// element and the others follow it, wrapping back to slot 0 after the last slot. When the buffer
+// is full, push_back() overwrites the oldest element and returns it, so the buffer always keeps
+// the newest `capacity` elements.
+//
+//The invariants that the unsafe code relies on are:
+// 1) `head < capacity` whenever capacity > 0, and `len <= capacity`.
+// 2) The `len` slots starting at `head` (wrapping around) are initialized, the rest are not.
+
+use std::mem::MaybeUninit;
+
+pub struct RingBuffer<T> {
+    slots: Box<[MaybeUninit<T>]>,
+    head: usize,
+    len: usize,
+}
+
+impl<T> RingBuffer<T> {
+    pub fn with_capacity(capacity: usize) -> RingBuffer<T> {
+        RingBuffer {
+            slots: (0..capacity).map(|_| MaybeUninit::uninit()).collect(),
+            head: 0,
+            len: 0,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == self.capacity()
+    }
+
+    //The slot of the element `offset` places after the oldest one. Written out instead of using
+    // `%` to make the wraparound easy to see, this only works because offset < capacity.
+    fn slot(&self, offset: usize) -> usize {
+        let index = self.head + offset;
+        if index >= self.capacity() {
+            index - self.capacity()
+        } else {
+            index
+        }
+    }
+
+    //Returns the element that was pushed out to make room, or None if there was space. With a
+    // capacity of 0 nothing can be stored, so the value itself is handed straight back.
+    pub fn push_back(&mut self, value: T) -> Option<T> {
+        if self.capacity() == 0 {
+            return Some(value);
+        }
+
+        if self.is_full() {
+            //The oldest slot is also the slot that comes after the newest one, so the new value
+            // goes there and head moves forward by one.
+            let slot = self.head;
+            //Safety: the buffer is full so every slot is initialized, including head. The old
+            // value is read out before the slot is overwritten so it is not dropped twice.
+            let evicted = unsafe { self.slots[slot].assume_init_read() };
+            self.slots[slot].write(value);
+            self.head = self.slot(1);
+            return Some(evicted);
+        }
+
+        let slot = self.slot(self.len);
+        self.slots[slot].write(value);
+        self.len += 1;
+        None
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let slot = self.head;
+        //Safety: len > 0 so head is initialized. It is treated as uninitialized after this since
+        // head and len are moved past it.
+        let value = unsafe { self.slots[slot].assume_init_read() };
+        self.head = self.slot(1);
+        self.len -= 1;
+        Some(value)
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            buffer: self,
+            offset: 0,
+        }
+    }
+}
+
+impl<T> Drop for RingBuffer<T> {
+    fn drop(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+}
+
+//Walks from the oldest element to the newest, across the wrap point.
+pub struct Iter<'a, T> {
+    buffer: &'a RingBuffer<T>,
+    offset: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.offset == self.buffer.len {
+            return None;
+        }
+
+        let slot = self.buffer.slot(self.offset);
+        self.offset += 1;
+        //Safety: offset < len, so the slot is one of the initialized ones (invariant 2).
+        Some(unsafe { self.buffer.slots[slot].assume_init_ref() })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.buffer.len - self.offset;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    fn contents<T: Clone>(buffer: &RingBuffer<T>) -> Vec<T> {
+        buffer.iter().cloned().collect()
+    }
+
+    #[test]
+    fn fill_then_evict_the_oldest() {
+        let mut buffer = RingBuffer::with_capacity(3);
+        assert_eq!(buffer.push_back(1), None);
+        assert_eq!(buffer.push_back(2), None);
+        assert_eq!(buffer.push_back(3), None);
+        assert!(buffer.is_full());
+
+        assert_eq!(buffer.push_back(4), Some(1));
+        assert_eq!(contents(&buffer), [2, 3, 4]);
+    }
+
+    #[test]
+    fn wraps_around_several_times() {
+        let mut buffer = RingBuffer::with_capacity(4);
+        for i in 0..11 {
+            buffer.push_back(i);
+        }
+
+        assert_eq!(buffer.len(), 4);
+        assert_eq!(contents(&buffer), [7, 8, 9, 10]);
+        assert_eq!(buffer.iter().len(), 4);
+    }
+
+    #[test]
+    fn interleaved_push_and_pop() {
+        let mut buffer = RingBuffer::with_capacity(3);
+        buffer.push_back('a');
+        buffer.push_back('b');
+        assert_eq!(buffer.pop_front(), Some('a'));
+        buffer.push_back('c');
+        buffer.push_back('d');
+        assert_eq!(contents(&buffer), ['b', 'c', 'd']);
+
+        assert_eq!(buffer.pop_front(), Some('b'));
+        buffer.push_back('e');
+        assert_eq!(buffer.push_back('f'), Some('c'));
+        assert_eq!(contents(&buffer), ['d', 'e', 'f']);
+
+        assert_eq!(buffer.pop_front(), Some('d'));
+        assert_eq!(buffer.pop_front(), Some('e'));
+        assert_eq!(buffer.pop_front(), Some('f'));
+        assert_eq!(buffer.pop_front(), None);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn zero_capacity_hands_the_value_back() {
+        let mut buffer = RingBuffer::with_capacity(0);
+
+        assert_eq!(buffer.push_back(5), Some(5));
+        assert!(buffer.is_empty());
+        assert!(buffer.is_full());
+    }
+
+    #[test]
+    fn drop_releases_only_stored_values() {
+        let shared = Rc::new(());
+        {
+            let mut buffer = RingBuffer::with_capacity(2);
+            for _ in 0..5 {
+                buffer.push_back(Rc::clone(&shared));
+            }
+            assert_eq!(Rc::strong_count(&shared), 3);
+        }
+        assert_eq!(Rc::strong_count(&shared), 1);
+    }
+}
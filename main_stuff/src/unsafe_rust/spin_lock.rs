@@ -0,0 +1,159 @@
+//A lock written with atomics instead of the operating system. A thread that wants the lock keeps
+// trying to flip `locked` from false to true until it manages to, spinning in a loop while it
+// waits. This is only sensible when the lock is held for a very short time, but it shows how a
+// Mutex can be built as a safe abstraction over unsafe code.
+//
+//The invariant is that whoever flipped `locked` to true has the only access to `value` until it
+// is set back to false. The only way to flip it is through lock() or try_lock(), and the only way
+// to set it back is by dropping the SpinLockGuard that they return.
+
+use std::cell::UnsafeCell;
+use std::hint;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+pub struct SpinLock<T> {
+    locked: AtomicBool,
+    value: UnsafeCell<T>,
+}
+
+//Safety: the lock makes sure that only one thread can reach the value at a time, so sharing a
+// SpinLock between threads is the same as sending the value to whichever thread holds the lock.
+// That is why T only needs to be Send and not Sync.
+unsafe impl<T: Send> Sync for SpinLock<T> {}
+
+/// Access to the value inside of a SpinLock, which is unlocked again when the guard is dropped.
+///
+/// Sharing a guard between threads shares the value itself, so that is only allowed when the value
+/// is Sync. A guard of a `Cell` cannot be shared, otherwise two threads could set it at once.
+///
+/// ```compile_fail
+/// use std::cell::Cell;
+/// use std::thread;
+/// use rust_book_chapter_19::unsafe_rust::spin_lock::SpinLock;
+///
+/// let lock = SpinLock::new(Cell::new(0_u64));
+/// let guard = lock.lock();
+/// thread::scope(|scope| {
+///     scope.spawn(|| guard.set(1));
+///     scope.spawn(|| guard.set(2));
+/// });
+/// ```
+pub struct SpinLockGuard<'a, T> {
+    lock: &'a SpinLock<T>,
+    //A raw pointer is neither Send nor Sync, which turns off the automatic impls. Without it the
+    // guard would be Sync whenever &SpinLock<T> is, which only needs T: Send.
+    marker: PhantomData<*const ()>,
+}
+
+//Safety: moving the guard to another thread moves the only access to the value along with it,
+// the same as sending a &mut T. Nothing about unlocking is tied to the thread that locked it.
+unsafe impl<T: Send> Send for SpinLockGuard<'_, T> {}
+
+//Safety: a shared guard only hands out &T, so sharing it is the same as sharing a &T, which is
+// only fine when T is Sync. std's MutexGuard uses the same bound.
+unsafe impl<T: Sync> Sync for SpinLockGuard<'_, T> {}
+
+impl<T> SpinLock<T> {
+    pub const fn new(value: T) -> SpinLock<T> {
+        SpinLock {
+            locked: AtomicBool::new(false),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    pub fn lock(&self) -> SpinLockGuard<'_, T> {
+        loop {
+            if let Some(guard) = self.try_lock() {
+                return guard;
+            }
+            //Waiting on a plain load keeps the cache line shared until the lock looks free,
+            // instead of every waiting thread fighting over it with compare_exchange.
+            while self.locked.load(Ordering::Relaxed) {
+                hint::spin_loop();
+            }
+        }
+    }
+
+    //Acquire makes everything that the previous holder wrote to the value visible to this thread.
+    pub fn try_lock(&self) -> Option<SpinLockGuard<'_, T>> {
+        self.locked
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .ok()
+            .map(|_| SpinLockGuard {
+                lock: self,
+                marker: PhantomData,
+            })
+    }
+
+    //Having the SpinLock by value means nobody else can be holding the lock.
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+}
+
+impl<T> Deref for SpinLockGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        //Safety: the guard only exists while this thread holds the lock.
+        unsafe { &*self.lock.value.get() }
+    }
+}
+
+impl<T> DerefMut for SpinLockGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        //Safety: the guard only exists while this thread holds the lock, and the mutable borrow
+        // of the guard stops any other reference to the value being made through it.
+        unsafe { &mut *self.lock.value.get() }
+    }
+}
+
+//Release makes the writes made while holding the lock visible to the next thread that acquires it.
+impl<T> Drop for SpinLockGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.locked.store(false, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn threads_increment_a_shared_counter() {
+        let counter = Arc::new(SpinLock::new(0_u64));
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let counter = Arc::clone(&counter);
+                thread::spawn(move || {
+                    for _ in 0..1000 {
+                        *counter.lock() += 1;
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(*counter.lock(), 8000);
+    }
+
+    #[test]
+    fn try_lock_fails_while_a_guard_is_held() {
+        let lock = SpinLock::new(String::from("a"));
+
+        let mut guard = lock.try_lock().expect("lock should be free");
+        guard.push('b');
+        assert!(lock.try_lock().is_none());
+
+        drop(guard);
+        assert_eq!(*lock.try_lock().expect("lock should be free again"), "ab");
+        assert_eq!(lock.into_inner(), "ab");
+    }
+}
@@ -0,0 +1,180 @@
+//A Vec that keeps its elements in an array instead of on the heap, so the capacity is fixed at
+// compile time through a const generic. `StackVec<String, 4>` and `StackVec<String, 8>` are two
+// different types.
+//
+//The array starts out uninitialized. MaybeUninit<T> is a slot that may or may not hold a valid T,
+// and it never drops what is inside of it, which leaves that up to StackVec.
+//The invariant is that the first `len` slots are initialized and the rest are not, with
+// `len <= N` always.
+
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::mem::MaybeUninit;
+use std::ops::{Deref, DerefMut};
+use std::ptr;
+use std::slice;
+
+pub struct StackVec<T, const N: usize> {
+    items: [MaybeUninit<T>; N],
+    len: usize,
+}
+
+//Returned by push() when the StackVec is already full. The value that did not fit is handed back
+// instead of being dropped.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CapacityError<T> {
+    pub value: T,
+    pub capacity: usize,
+}
+
+impl<T> Display for CapacityError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "StackVec is full, its capacity is {}", self.capacity)
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for CapacityError<T> {}
+
+impl<T, const N: usize> StackVec<T, N> {
+    pub const fn new() -> StackVec<T, N> {
+        StackVec {
+            //An array of MaybeUninit does not need to be initialized, so each slot can start
+            // out uninitialized.
+            items: [const { MaybeUninit::uninit() }; N],
+            len: 0,
+        }
+    }
+
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn push(&mut self, value: T) -> Result<(), CapacityError<T>> {
+        if self.len == N {
+            return Err(CapacityError { value, capacity: N });
+        }
+
+        self.items[self.len].write(value);
+        self.len += 1;
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+        //Safety: the slot was below len so it is initialized. len has already been lowered, so
+        // the slot counts as uninitialized from now on and the value is only read out once.
+        Some(unsafe { self.items[self.len].assume_init_read() })
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        //Safety: the first len slots are initialized, and MaybeUninit<T> has the same layout as T.
+        unsafe { slice::from_raw_parts(self.items.as_ptr() as *const T, self.len) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        //Safety: the same as as_slice().
+        unsafe { slice::from_raw_parts_mut(self.items.as_mut_ptr() as *mut T, self.len) }
+    }
+}
+
+impl<T, const N: usize> Default for StackVec<T, N> {
+    fn default() -> StackVec<T, N> {
+        StackVec::new()
+    }
+}
+
+impl<T, const N: usize> Deref for StackVec<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const N: usize> DerefMut for StackVec<T, N> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+//Only the initialized prefix is dropped. Dropping the uninitialized slots would be reading
+// garbage as if it were a T.
+impl<T, const N: usize> Drop for StackVec<T, N> {
+    fn drop(&mut self) {
+        //Safety: the slice covers exactly the initialized slots, and they are never used again.
+        unsafe {
+            ptr::drop_in_place(self.as_mut_slice());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    struct DropCounter<'a>(&'a Cell<usize>);
+
+    impl Drop for DropCounter<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn push_to_capacity_then_overflow() {
+        let mut stack: StackVec<String, 3> = StackVec::new();
+        for word in ["a", "b", "c"] {
+            assert!(stack.push(word.to_string()).is_ok());
+        }
+
+        let error = stack.push(String::from("d")).unwrap_err();
+        assert_eq!(error.value, "d");
+        assert_eq!(error.to_string(), "StackVec is full, its capacity is 3");
+        assert_eq!(stack.as_slice(), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn pop_and_deref() {
+        let mut stack: StackVec<i32, 4> = StackVec::new();
+        stack.push(1).unwrap();
+        stack.push(2).unwrap();
+        stack[0] = 10;
+
+        assert_eq!(stack.iter().sum::<i32>(), 12);
+        assert_eq!(stack.pop(), Some(2));
+        assert_eq!(stack.pop(), Some(10));
+        assert_eq!(stack.pop(), None);
+        assert!(stack.is_empty());
+        assert_eq!(stack.capacity(), 4);
+    }
+
+    #[test]
+    fn drop_only_drops_initialized_items() {
+        let drops = Cell::new(0);
+        {
+            let mut stack: StackVec<DropCounter, 8> = StackVec::new();
+            for _ in 0..3 {
+                stack.push(DropCounter(&drops)).ok().unwrap();
+            }
+            drop(stack.pop());
+            assert_eq!(drops.get(), 1);
+        }
+        assert_eq!(drops.get(), 3);
+    }
+
+    #[test]
+    fn rejected_value_is_dropped_once() {
+        let drops = Cell::new(0);
+        let mut stack: StackVec<DropCounter, 0> = StackVec::new();
+
+        let error = stack.push(DropCounter(&drops)).err().unwrap();
+        assert_eq!(drops.get(), 0);
+        drop(error);
+        assert_eq!(drops.get(), 1);
+    }
+}
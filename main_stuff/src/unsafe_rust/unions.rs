@@ -0,0 +1,112 @@
+//A union is like a struct except that all of its fields share the same memory, so only one of
+// them holds a real value at a time. Rust has no way of knowing which one that is, so reading a
+// field of a union is unsafe. The usual fix is to store a tag next to the union that says which
+// field was last written, which is what `C` code does by hand and what a Rust enum does
+// automatically.
+
+//repr(C) gives the union the same layout as the equivalent `C` union. Every field is 4 bytes so
+// all of the bytes are always initialized no matter which field was written.
+#[repr(C)]
+#[derive(Clone, Copy)]
+union RawValue {
+    i: i32,
+    f: f32,
+    bytes: [u8; 4],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Int,
+    Float,
+}
+
+//The union stays private so that the only way to read it is through the accessors below, which
+// check the tag first.
+#[derive(Clone, Copy)]
+pub struct Value {
+    kind: Kind,
+    raw: RawValue,
+}
+
+impl Value {
+    pub fn from_i32(i: i32) -> Value {
+        Value {
+            kind: Kind::Int,
+            raw: RawValue { i },
+        }
+    }
+
+    pub fn from_f32(f: f32) -> Value {
+        Value {
+            kind: Kind::Float,
+            raw: RawValue { f },
+        }
+    }
+
+    pub fn kind(&self) -> Kind {
+        self.kind
+    }
+
+    pub fn as_i32(&self) -> Option<i32> {
+        match self.kind {
+            //Safety: the tag says that `i` was the field that was written.
+            Kind::Int => Some(unsafe { self.raw.i }),
+            Kind::Float => None,
+        }
+    }
+
+    pub fn as_f32(&self) -> Option<f32> {
+        match self.kind {
+            //Safety: the tag says that `f` was the field that was written.
+            Kind::Float => Some(unsafe { self.raw.f }),
+            Kind::Int => None,
+        }
+    }
+
+    //The stored value as little endian bytes, whichever kind it is. Reading the bytes field is
+    // always fine because every field covers all 4 bytes. The bytes come out in the machine's own
+    // order, so they are put back together and then written out as little endian.
+    pub fn to_le_bytes(&self) -> [u8; 4] {
+        //Safety: all 4 bytes are initialized by either constructor, and any bytes are a valid
+        // [u8; 4].
+        let native = unsafe { self.raw.bytes };
+        u32::from_ne_bytes(native).to_le_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips() {
+        for i in [0, 1, -1, i32::MAX, i32::MIN] {
+            assert_eq!(Value::from_i32(i).as_i32(), Some(i));
+        }
+        for f in [0.0, -1.5, f32::MAX, f32::INFINITY] {
+            assert_eq!(Value::from_f32(f).as_f32(), Some(f));
+        }
+        assert!(Value::from_f32(f32::NAN).as_f32().unwrap().is_nan());
+    }
+
+    #[test]
+    fn mismatched_accessors_are_none() {
+        let int = Value::from_i32(7);
+        let float = Value::from_f32(7.0);
+
+        assert_eq!(int.kind(), Kind::Int);
+        assert_eq!(int.as_f32(), None);
+        assert_eq!(float.kind(), Kind::Float);
+        assert_eq!(float.as_i32(), None);
+    }
+
+    #[test]
+    fn bytes_match_the_primitive() {
+        for i in [0, 258, -2, i32::MIN] {
+            assert_eq!(Value::from_i32(i).to_le_bytes(), i.to_le_bytes());
+        }
+        for f in [1.0_f32, -0.0, 3.25] {
+            assert_eq!(Value::from_f32(f).to_le_bytes(), f.to_le_bytes());
+        }
+    }
+}
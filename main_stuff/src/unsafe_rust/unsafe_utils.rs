@@ -0,0 +1,153 @@
+//Small building blocks for unsafe code that did not fit anywhere else, along with safe versions
+// of things that are often done with unsafe code.
+
+use std::alloc::{self, Layout};
+use std::marker::PhantomData;
+use std::mem;
+use std::ptr::NonNull;
+
+//A Box written by hand. NonNull<T> is a raw pointer that is never null, which lets the compiler
+// use null to represent None. So Option<NonNullBox<T>> is the same size as a plain pointer, just
+// like Option<Box<T>> is.
+//
+//The invariant is that `ptr` always points to a valid, initialized T that this box owns. For zero
+// sized types nothing is allocated and `ptr` is dangling, which is still fine to read a ZST from.
+pub struct NonNullBox<T> {
+    ptr: NonNull<T>,
+    //The box owns a T, which matters for drop checking.
+    _marker: PhantomData<T>,
+}
+
+//Safety: NonNullBox owns its value in the same way that Box does.
+unsafe impl<T: Send> Send for NonNullBox<T> {}
+unsafe impl<T: Sync> Sync for NonNullBox<T> {}
+
+impl<T> NonNullBox<T> {
+    pub fn new(val: T) -> NonNullBox<T> {
+        let ptr = if mem::size_of::<T>() == 0 {
+            NonNull::dangling()
+        } else {
+            let layout = Layout::new::<T>();
+            //Safety: the layout has a non-zero size because T is not zero sized.
+            let raw = unsafe { alloc::alloc(layout) } as *mut T;
+            NonNull::new(raw).unwrap_or_else(|| alloc::handle_alloc_error(layout))
+        };
+
+        //Safety: ptr is aligned and valid for writes, and the memory is not initialized yet so
+        // nothing is overwritten without being dropped.
+        unsafe {
+            ptr.as_ptr().write(val);
+        }
+
+        NonNullBox {
+            ptr,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn get(&self) -> &T {
+        //Safety: ptr points to a valid T and the shared borrow of self keeps it alive.
+        unsafe { self.ptr.as_ref() }
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        //Safety: ptr points to a valid T and the mutable borrow of self means nothing else can
+        // reach it.
+        unsafe { self.ptr.as_mut() }
+    }
+}
+
+impl<T> Drop for NonNullBox<T> {
+    fn drop(&mut self) {
+        //Safety: the value is valid and is never used again after this.
+        unsafe {
+            self.ptr.as_ptr().drop_in_place();
+        }
+
+        if mem::size_of::<T>() != 0 {
+            //Safety: the memory was allocated in new() with this same layout.
+            unsafe {
+                alloc::dealloc(self.ptr.as_ptr() as *mut u8, Layout::new::<T>());
+            }
+        }
+    }
+}
+
+//An i32 can be looked at as its 4 bytes without any unsafe code at all. The unsafe way would be
+// `mem::transmute::<i32, [u8; 4]>(val)`, which compiles down to the same thing but leaves it up
+// to the programmer to get the sizes right. to_ne_bytes() is checked by the compiler instead.
+//"ne" is native endian, the byte order of the machine it runs on, which is what transmute would
+// give as well. The bytes come back by value, handing out a `&[u8; 4]` that points into the i32
+// would need a pointer cast, which is what layout::bytes_of() does.
+pub fn as_bytes_i32(val: &i32) -> [u8; 4] {
+    val.to_ne_bytes()
+}
+
+pub fn from_bytes_i32(bytes: &[u8; 4]) -> i32 {
+    i32::from_ne_bytes(*bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn new_and_get() {
+        let boxed = NonNullBox::new(String::from("hello"));
+
+        assert_eq!(boxed.get(), "hello");
+    }
+
+    #[test]
+    fn get_mut_changes_the_value() {
+        let mut boxed = NonNullBox::new(vec![1, 2]);
+        boxed.get_mut().push(3);
+
+        assert_eq!(boxed.get(), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn zero_sized_values_work() {
+        let mut boxed = NonNullBox::new(());
+        *boxed.get_mut() = ();
+
+        assert_eq!(boxed.get(), &());
+    }
+
+    #[test]
+    fn drop_drops_the_value() {
+        let shared = Rc::new(5);
+        let boxed = NonNullBox::new(Rc::clone(&shared));
+        assert_eq!(Rc::strong_count(&shared), 2);
+
+        drop(boxed);
+        assert_eq!(Rc::strong_count(&shared), 1);
+    }
+
+    #[test]
+    fn i32_bytes_round_trip() {
+        for value in [0, 1, -1, 42, -1000, i32::MIN, i32::MAX] {
+            assert_eq!(from_bytes_i32(&as_bytes_i32(&value)), value);
+        }
+    }
+
+    #[test]
+    fn i32_bytes_are_native_endian() {
+        let bytes = as_bytes_i32(&0x0102_0304);
+
+        if cfg!(target_endian = "little") {
+            assert_eq!(bytes, [4, 3, 2, 1]);
+        } else {
+            assert_eq!(bytes, [1, 2, 3, 4]);
+        }
+        assert_eq!(bytes, crate::unsafe_rust::layout::bytes_of(&0x0102_0304_i32));
+        assert_eq!(as_bytes_i32(&-1), [0xff; 4]);
+    }
+
+    #[test]
+    fn option_uses_the_null_niche() {
+        assert_eq!(mem::size_of::<Option<NonNullBox<u64>>>(), mem::size_of::<*const u64>());
+        assert_eq!(mem::size_of::<Option<NonNullBox<u64>>>(), mem::size_of::<Option<Box<u64>>>());
+    }
+}
@@ -0,0 +1,124 @@
+//Normally the compiler is free to skip a read or write if it can tell the result is not needed,
+// or to merge several writes into one. That is fine for regular memory but not for memory-mapped
+// hardware, where writing to an address can start a device and reading from one can return a
+// different value every time. ptr::read_volatile() and ptr::write_volatile() tell the compiler
+// that each access matters and must actually happen, in order.
+
+use std::cell::UnsafeCell;
+use std::ptr;
+
+//repr(transparent) means a Volatile<T> has exactly the same layout as a T, so a struct of them
+// can be laid over real registers. The value is inside of an UnsafeCell because hardware registers
+// are written through shared references, which is only allowed through an UnsafeCell.
+#[repr(transparent)]
+pub struct Volatile<T: Copy> {
+    value: UnsafeCell<T>,
+}
+
+impl<T: Copy> Volatile<T> {
+    pub fn new(value: T) -> Volatile<T> {
+        Volatile {
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    pub fn read(&self) -> T {
+        //Safety: the pointer from the UnsafeCell is valid and aligned for as long as self is.
+        unsafe { ptr::read_volatile(self.value.get()) }
+    }
+
+    pub fn write(&self, value: T) {
+        //Safety: the same as read(). Volatile is not Sync, so no other thread can be accessing the
+        // value at the same time.
+        unsafe { ptr::write_volatile(self.value.get(), value) }
+    }
+}
+
+//A made up device with three 32 bit registers. repr(C) keeps the fields in this order with no
+// reordering, so they sit at offsets 0, 4 and 8 just like the device documentation would say.
+#[repr(C)]
+pub struct MappedRegisters {
+    pub control: Volatile<u32>,
+    pub status: Volatile<u32>,
+    pub data: Volatile<u32>,
+}
+
+impl MappedRegisters {
+    //Registers backed by regular memory, which is useful for trying things out without hardware.
+    pub fn new() -> MappedRegisters {
+        MappedRegisters {
+            control: Volatile::new(0),
+            status: Volatile::new(0),
+            data: Volatile::new(0),
+        }
+    }
+
+    /// Treats the memory at `ptr` as a set of registers, which is how a driver would get at a
+    /// device mapped to a fixed address.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be non-null, aligned for u32 and point to memory that is valid for reads and
+    /// writes of a MappedRegisters for the whole lifetime `'a`. Nothing else may access that memory
+    /// through a non-volatile reference during `'a`.
+    pub unsafe fn from_ptr<'a>(ptr: *mut MappedRegisters) -> &'a MappedRegisters {
+        &*ptr
+    }
+
+    //Writing the data register and then setting the start bit in control. With volatile writes
+    // these are guaranteed to both happen, in this order.
+    pub fn send(&self, value: u32) {
+        self.data.write(value);
+        self.control.write(self.control.read() | 1);
+    }
+}
+
+impl Default for MappedRegisters {
+    fn default() -> MappedRegisters {
+        MappedRegisters::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem;
+
+    #[test]
+    fn reads_reflect_writes() {
+        let cell = Volatile::new(1_u64);
+        assert_eq!(cell.read(), 1);
+
+        cell.write(7);
+        cell.write(9);
+        assert_eq!(cell.read(), 9);
+    }
+
+    #[test]
+    fn volatile_has_the_layout_of_its_value() {
+        assert_eq!(mem::size_of::<Volatile<u32>>(), mem::size_of::<u32>());
+        assert_eq!(mem::align_of::<Volatile<u16>>(), mem::align_of::<u16>());
+    }
+
+    #[test]
+    fn register_offsets_match_repr_c() {
+        assert_eq!(mem::offset_of!(MappedRegisters, control), 0);
+        assert_eq!(mem::offset_of!(MappedRegisters, status), 4);
+        assert_eq!(mem::offset_of!(MappedRegisters, data), 8);
+        assert_eq!(mem::size_of::<MappedRegisters>(), 12);
+    }
+
+    #[test]
+    fn registers_over_raw_memory() {
+        let mut memory = [0_u32; 3];
+        //Safety: the array is aligned for u32, is the size of MappedRegisters and is only used
+        // through `registers` until the end of the block.
+        let registers = unsafe { MappedRegisters::from_ptr(memory.as_mut_ptr().cast()) };
+
+        registers.status.write(0x80);
+        registers.send(42);
+        assert_eq!(registers.control.read(), 1);
+
+        assert_eq!(memory, [1, 0x80, 42]);
+    }
+}
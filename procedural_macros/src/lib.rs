@@ -1,7 +1,8 @@
-use proc_macro;
 use proc_macro::TokenStream;
 use quote::quote;
-use syn;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
 
 #[proc_macro_derive(HelloMacro)]
 pub fn hello_macro_derive(input: TokenStream) -> TokenStream {
@@ -9,7 +10,8 @@ pub fn hello_macro_derive(input: TokenStream) -> TokenStream {
     // that we can manipulate
     let ast = syn::parse(input).unwrap();
 
-    // Build the trait implementation
+    // Build the trait implementation along with an inherent
+    // impl that gives the type its own name at runtime
     impl_hello_macro(&ast)
 }
 
@@ -18,9 +20,591 @@ fn impl_hello_macro(ast: &syn::DeriveInput) -> TokenStream {
     let gen = quote! {
         impl HelloMacro for #name {
             fn hello_macro() {
-                println!("Hello, Macro! My name is {}!", stringify!(#name));
+                Self::hello_macro_to(&mut ::std::io::stdout()).expect("failed to write to stdout");
             }
+
+            // Every greeting goes through here, so this is the one place
+            // that adds it to the greet_log when logging is turned on.
+            fn hello_macro_to<W: ::std::io::Write>(writer: &mut W) -> ::std::io::Result<()> {
+                let message = format!("Hello, Macro! My name is {}!", stringify!(#name));
+                ::procedural_trait::greet_log::record(&message);
+                writeln!(writer, "{}", message)
+            }
+
+            fn hello_macro_with_config(cfg: &::procedural_trait::Greeting) {
+                println!("{}", cfg.message(stringify!(#name)));
+            }
+
+            fn hello_macro_with_config_to<W: ::std::io::Write>(
+                cfg: &::procedural_trait::Greeting,
+                writer: &mut W,
+            ) -> ::std::io::Result<()> {
+                writeln!(writer, "{}", cfg.message(stringify!(#name)))
+            }
+        }
+
+        // The trait comment points out that Rust has no reflection to
+        // look up a type name at runtime, but the macro already knows
+        // the name at compile time so it can just hand it out.
+        impl #name {
+            pub fn type_name() -> &'static str {
+                stringify!(#name)
+            }
+        }
+    };
+    gen.into()
+}
+
+#[proc_macro_derive(FieldCount)]
+pub fn field_count_derive(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).unwrap();
+
+    impl_field_count(&ast)
+}
+
+fn impl_field_count(ast: &syn::DeriveInput) -> TokenStream {
+    // Named fields, tuple fields and unit structs are all covered
+    // by Fields::len(), a unit struct simply has none
+    let count = match &ast.data {
+        syn::Data::Struct(data) => data.fields.len(),
+        _ => {
+            return syn::Error::new_spanned(&ast.ident, "FieldCount can only be derived for structs")
+                .into_compile_error()
+                .into()
+        }
+    };
+
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let gen = quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            pub const FIELD_COUNT: usize = #count;
+        }
+    };
+    gen.into()
+}
+
+#[proc_macro_derive(Builder)]
+pub fn builder_derive(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).unwrap();
+
+    impl_builder(&ast)
+}
+
+// Generates a `<Name>Builder` with an Option for every field of the
+// struct. Each setter fills one in, and build() hands back the struct
+// or names the first field that was never set. Only structs with named
+// fields are supported, since the setters are named after the fields.
+fn impl_builder(ast: &syn::DeriveInput) -> TokenStream {
+    let fields = match &ast.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        _ => {
+            return syn::Error::new_spanned(
+                &ast.ident,
+                "Builder can only be derived for structs with named fields",
+            )
+            .into_compile_error()
+            .into()
+        }
+    };
+
+    let name = &ast.ident;
+    let vis = &ast.vis;
+    let builder = syn::Ident::new(&format!("{}Builder", name), name.span());
+    let generics = &ast.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let field_names: Vec<_> = fields.iter().map(|field| &field.ident).collect();
+    let field_types: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+    let missing: Vec<_> = field_names
+        .iter()
+        .map(|field| format!("field `{}` is not set", field.as_ref().unwrap()))
+        .collect();
+
+    let gen = quote! {
+        #vis struct #builder #generics #where_clause {
+            #(#field_names: ::std::option::Option<#field_types>,)*
+        }
+
+        impl #impl_generics #builder #ty_generics #where_clause {
+            #(
+                pub fn #field_names(mut self, value: #field_types) -> Self {
+                    self.#field_names = ::std::option::Option::Some(value);
+                    self
+                }
+            )*
+
+            pub fn build(self) -> ::std::result::Result<#name #ty_generics, ::std::string::String> {
+                ::std::result::Result::Ok(#name {
+                    #(
+                        #field_names: self.#field_names
+                            .ok_or_else(|| ::std::string::String::from(#missing))?,
+                    )*
+                })
+            }
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            pub fn builder() -> #builder #ty_generics {
+                #builder {
+                    #(#field_names: ::std::option::Option::None,)*
+                }
+            }
+        }
+    };
+    gen.into()
+}
+
+// `attributes(default2)` registers the helper attribute, so the compiler
+// lets `#[default2(...)]` sit on the fields and leaves it for this derive
+// to read instead of reporting it as unknown.
+#[proc_macro_derive(Default2, attributes(default2))]
+pub fn default2_derive(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).unwrap();
+
+    impl_default2(&ast)
+}
+
+// Generates a Default impl where each field is either the expression from
+// its `#[default2(value = ...)]` or Default::default() when it has none.
+fn impl_default2(ast: &syn::DeriveInput) -> TokenStream {
+    let fields = match &ast.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        _ => {
+            return syn::Error::new_spanned(
+                &ast.ident,
+                "Default2 can only be derived for structs with named fields",
+            )
+            .into_compile_error()
+            .into()
+        }
+    };
+
+    let mut values = Vec::new();
+    for field in fields {
+        match default2_value(field) {
+            Ok(Some(value)) => values.push(quote!(#value)),
+            Ok(None) => values.push(quote!(::std::default::Default::default())),
+            Err(error) => return error.into_compile_error().into(),
+        }
+    }
+
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let field_names = fields.iter().map(|field| &field.ident);
+    let gen = quote! {
+        impl #impl_generics ::std::default::Default for #name #ty_generics #where_clause {
+            fn default() -> Self {
+                #name {
+                    #(#field_names: #values,)*
+                }
+            }
+        }
+    };
+    gen.into()
+}
+
+// Reads the `value = ...` out of a field's default2 attribute. Anything
+// other than `value` inside of the parentheses is an error on that key,
+// and so is giving the attribute twice on one field.
+fn default2_value(field: &syn::Field) -> syn::Result<Option<syn::Expr>> {
+    let mut value = None;
+    for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("default2")) {
+        if value.is_some() {
+            return Err(syn::Error::new_spanned(attr, "default2 can only be given once per field"));
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("value") {
+                value = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `value = ...`"))
+            }
+        })?;
+        if value.is_none() {
+            return Err(syn::Error::new_spanned(attr, "expected `value = ...`"));
+        }
+    }
+    Ok(value)
+}
+
+#[proc_macro_derive(VariantName)]
+pub fn variant_name_derive(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).unwrap();
+
+    impl_variant_name(&ast)
+}
+
+// Generates a match with one arm per variant. Every arm uses the braced
+// `Name::Variant { .. }` pattern, which matches unit, tuple and struct
+// variants alike without binding any of the fields. Matching on `*self`
+// instead of `self` means an enum with no variants still compiles, since
+// an empty match is only allowed on a value of the uninhabited type
+// itself and not on a reference to it.
+fn impl_variant_name(ast: &syn::DeriveInput) -> TokenStream {
+    let variants = match &ast.data {
+        syn::Data::Enum(data) => &data.variants,
+        _ => {
+            return syn::Error::new_spanned(&ast.ident, "VariantName can only be derived for enums")
+                .into_compile_error()
+                .into()
+        }
+    };
+
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let variant_idents: Vec<_> = variants.iter().map(|variant| &variant.ident).collect();
+    let gen = quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            pub fn variant_name(&self) -> &'static str {
+                match *self {
+                    #(Self::#variant_idents { .. } => stringify!(#variant_idents),)*
+                }
+            }
+        }
+    };
+    gen.into()
+}
+
+// An attribute macro gets two token streams, the arguments inside of
+// the attribute's parentheses and the item the attribute is on. The
+// item is handed back with its body moved inside of an unsafe block,
+// so a function that only wraps FFI calls does not need one of its own.
+// The signature is left alone, which means the function is still safe
+// to call, so it should only be used where every input is fine to pass
+// along.
+#[proc_macro_attribute]
+pub fn unsafe_wrapped(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        return syn::Error::new(
+            proc_macro::Span::call_site().into(),
+            "unsafe_wrapped does not take any arguments",
+        )
+        .into_compile_error()
+        .into();
+    }
+
+    let function = syn::parse_macro_input!(item as syn::ItemFn);
+
+    impl_unsafe_wrapped(&function)
+}
+
+fn impl_unsafe_wrapped(function: &syn::ItemFn) -> TokenStream {
+    let attrs = &function.attrs;
+    let vis = &function.vis;
+    let sig = &function.sig;
+    let stmts = &function.block.stmts;
+    let gen = quote! {
+        #(#attrs)*
+        #[allow(unused_unsafe)]
+        #vis #sig {
+            unsafe {
+                #(#stmts)*
+            }
+        }
+    };
+    gen.into()
+}
+
+// Counts how many times a function is called. The count is kept in a
+// thread_local next to the function, so each thread counts its own calls
+// and no locking is needed, and `<name>_call_count()` is generated to read
+// it. Because the counter is a static, this is for free functions only.
+// A static cannot be declared inside of an impl block. A method that takes
+// self is rejected with an error, but an associated function without self
+// looks exactly like a free function from here, so that one only fails
+// later when the generated code cannot find its counter.
+#[proc_macro_attribute]
+pub fn trace_calls(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        return syn::Error::new(
+            proc_macro::Span::call_site().into(),
+            "trace_calls does not take any arguments",
+        )
+        .into_compile_error()
+        .into();
+    }
+
+    let function = syn::parse_macro_input!(item as syn::ItemFn);
+
+    impl_trace_calls(&function)
+}
+
+fn impl_trace_calls(function: &syn::ItemFn) -> TokenStream {
+    if let Some(receiver) = function.sig.receiver() {
+        return syn::Error::new_spanned(receiver, "trace_calls is for free functions only, not methods")
+            .into_compile_error()
+            .into();
+    }
+
+    let attrs = &function.attrs;
+    let vis = &function.vis;
+    let sig = &function.sig;
+    let stmts = &function.block.stmts;
+    let name = &sig.ident;
+    let counter = syn::Ident::new(
+        &format!("__TRACE_CALLS_{}", name.to_string().to_uppercase()),
+        name.span(),
+    );
+    let count_fn = syn::Ident::new(&format!("{}_call_count", name), name.span());
+    let gen = quote! {
+        ::std::thread_local! {
+            #[doc(hidden)]
+            static #counter: ::std::cell::Cell<usize> = const { ::std::cell::Cell::new(0) };
+        }
+
+        #(#attrs)*
+        #vis #sig {
+            #counter.with(|calls| calls.set(calls.get() + 1));
+            #(#stmts)*
+        }
+
+        // How many times the function has been called on the current thread.
+        #vis fn #count_fn() -> usize {
+            #counter.with(::std::cell::Cell::get)
+        }
+    };
+    gen.into()
+}
+
+// The input to map!, an optional `<Key, Value>` followed by `key: value`
+// entries separated by commas.
+struct MapInput {
+    types: Option<(syn::Type, syn::Type)>,
+    entries: Punctuated<MapEntry, syn::Token![,]>,
+}
+
+struct MapEntry {
+    key: syn::Expr,
+    value: syn::Expr,
+}
+
+impl Parse for MapInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let types = if input.peek(syn::Token![<]) {
+            input.parse::<syn::Token![<]>()?;
+            let key = input.parse()?;
+            input.parse::<syn::Token![,]>()?;
+            let value = input.parse()?;
+            input.parse::<syn::Token![>]>()?;
+            Some((key, value))
+        } else {
+            None
+        };
+        let entries = Punctuated::parse_terminated(input)?;
+        Ok(MapInput { types, entries })
+    }
+}
+
+impl Parse for MapEntry {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key: syn::Expr = input.parse()?;
+        // The default error would only say "expected `:`", this one also
+        // points at the key so it is clear which entry is broken.
+        if !input.peek(syn::Token![:]) {
+            return Err(syn::Error::new(
+                key.span(),
+                "expected `:` after this key, entries are written as `key: value`",
+            ));
+        }
+        input.parse::<syn::Token![:]>()?;
+        let value = input.parse()?;
+        Ok(MapEntry { key, value })
+    }
+}
+
+/// Builds a HashMap from `key: value` entries, with the key and value
+/// types optionally given up front.
+///
+/// ```
+/// use procedural_macros::map;
+///
+/// let ages = map! { <&str, u8> "ferris": 8, "corro": 5 };
+/// assert_eq!(ages["ferris"], 8);
+/// ```
+///
+/// A missing colon is reported on the key of the broken entry.
+///
+/// ```compile_fail
+/// use procedural_macros::map;
+///
+/// let broken = map! { "x": 1, "y" 2 };
+/// ```
+///
+/// So is a literal key that is given twice.
+///
+/// ```compile_fail
+/// use procedural_macros::map;
+///
+/// let twice = map! { "x": 1, "x": 2 };
+/// ```
+#[proc_macro]
+pub fn map(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as MapInput);
+
+    impl_map(&input)
+}
+
+// A literal key that shows up twice would silently lose the first value,
+// so it is turned into an error on the second one. Keys that are not
+// literals can only be compared at runtime and are left alone.
+fn impl_map(input: &MapInput) -> TokenStream {
+    let mut seen = Vec::new();
+    for entry in &input.entries {
+        if let syn::Expr::Lit(lit) = &entry.key {
+            let text = quote!(#lit).to_string();
+            if seen.contains(&text) {
+                return syn::Error::new_spanned(lit, format!("duplicate key {} in map!", text))
+                    .into_compile_error()
+                    .into();
+            }
+            seen.push(text);
+        }
+    }
+
+    let len = input.entries.len();
+    let keys = input.entries.iter().map(|entry| &entry.key);
+    let values = input.entries.iter().map(|entry| &entry.value);
+    let map_type = match &input.types {
+        Some((key, value)) => quote!(::std::collections::HashMap<#key, #value>),
+        None => quote!(::std::collections::HashMap<_, _>),
+    };
+    let gen = quote! {
+        {
+            let mut map: #map_type = ::std::collections::HashMap::with_capacity(#len);
+            #(map.insert(#keys, #values);)*
+            map
         }
     };
     gen.into()
-}
\ No newline at end of file
+}
+
+/// Turns union-like syntax into an enum with one variant per field, plus
+/// an `as_<field>()` accessor for each one. An enum stores a tag along
+/// with the value, so unlike a union it is always safe to read.
+///
+/// ```
+/// use procedural_macros::tagged_union;
+///
+/// tagged_union!(Value { i: i32, f: f32 });
+///
+/// // Expands to `enum Value { I(i32), F(f32) }` with as_i() and as_f().
+/// assert_eq!(Value::I(5).as_i(), Some(&5));
+/// assert_eq!(Value::I(5).as_f(), None);
+/// ```
+#[proc_macro]
+pub fn tagged_union(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as TaggedUnionInput);
+
+    impl_tagged_union(&input)
+}
+
+// The input to tagged_union!, attributes and a visibility for the enum
+// followed by its name and `field: Type` pairs inside of braces.
+struct TaggedUnionInput {
+    attrs: Vec<syn::Attribute>,
+    vis: syn::Visibility,
+    name: syn::Ident,
+    fields: Punctuated<TaggedUnionField, syn::Token![,]>,
+}
+
+struct TaggedUnionField {
+    name: syn::Ident,
+    ty: syn::Type,
+}
+
+impl Parse for TaggedUnionInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        let name = input.parse()?;
+        let content;
+        syn::braced!(content in input);
+        let fields = Punctuated::parse_terminated(&content)?;
+        if fields.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &name,
+                "tagged_union! needs at least one field",
+            ));
+        }
+        Ok(TaggedUnionInput {
+            attrs,
+            vis,
+            name,
+            fields,
+        })
+    }
+}
+
+impl Parse for TaggedUnionField {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<syn::Token![:]>()?;
+        let ty = input.parse()?;
+        Ok(TaggedUnionField { name, ty })
+    }
+}
+
+// Turns a snake_case field name into the CamelCase name of its variant,
+// so `text` becomes `Text` and `big_int` becomes `BigInt`.
+fn variant_ident(field: &syn::Ident) -> syn::Ident {
+    let camel: String = field
+        .to_string()
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect();
+    syn::Ident::new(&camel, field.span())
+}
+
+// With a single field there is only one variant, so the catch-all arm
+// is left out instead of generating one that can never be reached.
+fn impl_tagged_union(input: &TaggedUnionInput) -> TokenStream {
+    let attrs = &input.attrs;
+    let vis = &input.vis;
+    let name = &input.name;
+    let types: Vec<_> = input.fields.iter().map(|field| &field.ty).collect();
+    let variants: Vec<_> = input
+        .fields
+        .iter()
+        .map(|field| variant_ident(&field.name))
+        .collect();
+    let accessors = input
+        .fields
+        .iter()
+        .map(|field| syn::Ident::new(&format!("as_{}", field.name), field.name.span()));
+    let other_variants = if variants.len() > 1 {
+        quote!(_ => None,)
+    } else {
+        quote!()
+    };
+    let gen = quote! {
+        #(#attrs)*
+        #vis enum #name {
+            #(#variants(#types),)*
+        }
+
+        impl #name {
+            #(
+                pub fn #accessors(&self) -> Option<&#types> {
+                    match self {
+                        #name::#variants(value) => Some(value),
+                        #other_variants
+                    }
+                }
+            )*
+        }
+    };
+    gen.into()
+}
@@ -0,0 +1,30 @@
+//A record of every greeting made by a derived HelloMacro, which is handy for checking what was
+// greeted when the greetings happen on other threads. Logging is off until it is turned on with
+// set_enabled(), so normal use does not keep collecting messages forever.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static LOG: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::SeqCst);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::SeqCst)
+}
+
+//Called by the code that the derive macro generates. A poisoned lock only means another thread
+// panicked while pushing, the Vec inside is still fine to use.
+pub fn record(message: &str) {
+    if is_enabled() {
+        LOG.lock().unwrap_or_else(|p| p.into_inner()).push(message.to_string());
+    }
+}
+
+//Hands back everything logged so far and empties the log.
+pub fn take_log() -> Vec<String> {
+    std::mem::take(&mut *LOG.lock().unwrap_or_else(|p| p.into_inner()))
+}
@@ -1,6 +1,173 @@
+use std::any;
+use std::io;
+use std::io::Write;
+
+//A log of greetings from derived HelloMacro types, see greet_log::take_log().
+pub mod greet_log;
+
+//Controls how hello_macro_with_config() greets. The default is "Hello, <name>!".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Greeting {
+    pub prefix: String,
+    pub exclaim: bool,
+}
+
+impl Greeting {
+    pub fn message(&self, name: &str) -> String {
+        let end = if self.exclaim { "!" } else { "." };
+        format!("{}, {}{}", self.prefix, name, end)
+    }
+}
+
+impl Default for Greeting {
+    fn default() -> Greeting {
+        Greeting {
+            prefix: String::from("Hello"),
+            exclaim: true,
+        }
+    }
+}
+
 pub trait HelloMacro {
     //Note that Rust does not have reflection so it cannot look up the typename at runtime. This
     // means that for this situation, a default implementation cannot work. However, a macro can
     // work.
     fn hello_macro();
+
+    //The same greeting written to any writer instead of stdout. This is what makes the greeting
+    // possible to check inside of tests. The default gets the name from any::type_name(), the
+    // same as hello_macro_with_config_to() below, so impls written before it existed still work.
+    fn hello_macro_to<W: Write>(writer: &mut W) -> io::Result<()> {
+        writeln!(writer, "{}", Greeting::default().message(short_type_name::<Self>()))
+    }
+
+    fn hello_macro_with_config(cfg: &Greeting)
+    where
+        Self: Sized,
+    {
+        Self::hello_macro_with_config_to(cfg, &mut io::stdout()).expect("failed to write to stdout");
+    }
+
+    //There is actually a way for a default implementation to get a name, any::type_name(). It is
+    // filled in by the compiler and not looked up at runtime, and it returns the full path such as
+    // `my_crate::module::MyStruct`, so only the last part is used. It is not guaranteed to be
+    // stable between compiler versions, which is why the derive macro replaces it with the name it
+    // sees in the source code.
+    fn hello_macro_with_config_to<W: Write>(cfg: &Greeting, writer: &mut W) -> io::Result<()>
+    where
+        Self: Sized,
+    {
+        writeln!(writer, "{}", cfg.message(short_type_name::<Self>()))
+    }
+}
+
+//The last part of any::type_name(), so `MyStruct` instead of `my_crate::module::MyStruct`.
+fn short_type_name<T: ?Sized>() -> &'static str {
+    let full_name = any::type_name::<T>();
+    full_name.rsplit("::").next().unwrap_or(full_name)
+}
+
+//Because hello_macro() is an associated function and not a method, no value of the type is
+// needed. The type itself is passed as a generic parameter, for example `greet::<MyStruct>()`.
+pub fn greet<T: HelloMacro>() {
+    T::hello_macro();
+}
+
+//The writer-based version of greet().
+pub fn greet_to<T: HelloMacro, W: Write>(writer: &mut W) -> io::Result<()> {
+    T::hello_macro_to(writer)
+}
+
+//hello_macro() belongs to the type, so it cannot be called on a value with `value.hello_macro()`.
+// This trait is the bridge. Every type that implements HelloMacro gets it through the blanket
+// impl below, so once a type derives HelloMacro its values can call `value.greet()`.
+pub trait IntoHelloMacro {
+    fn greet(&self);
+
+    fn greet_to<W: Write>(&self, writer: &mut W) -> io::Result<()>;
+}
+
+impl<T: HelloMacro> IntoHelloMacro for T {
+    fn greet(&self) {
+        T::hello_macro();
+    }
+
+    fn greet_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        T::hello_macro_to(writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Manual;
+
+    impl HelloMacro for Manual {
+        fn hello_macro() {
+            println!("Hello, Manual!");
+        }
+
+        fn hello_macro_to<W: Write>(writer: &mut W) -> io::Result<()> {
+            writeln!(writer, "Hello, Manual!")
+        }
+    }
+
+    //Only implements the required hello_macro(), the way impls did before hello_macro_to() existed.
+    struct OnlyHello;
+
+    impl HelloMacro for OnlyHello {
+        fn hello_macro() {
+            println!("Hello, OnlyHello!");
+        }
+    }
+
+    #[test]
+    fn default_hello_macro_to_uses_the_type_name() {
+        let mut output = Vec::new();
+        OnlyHello::hello_macro_to(&mut output).unwrap();
+
+        assert_eq!(String::from_utf8(output).unwrap(), "Hello, OnlyHello!\n");
+    }
+
+    #[test]
+    fn greeting_message_uses_prefix_and_exclaim() {
+        let calm = Greeting {
+            prefix: String::from("Good day"),
+            exclaim: false,
+        };
+
+        assert_eq!(Greeting::default().message("Manual"), "Hello, Manual!");
+        assert_eq!(calm.message("Manual"), "Good day, Manual.");
+    }
+
+    #[test]
+    fn default_config_greeting_uses_the_type_name() {
+        let cfg = Greeting {
+            prefix: String::from("Hi"),
+            exclaim: true,
+        };
+
+        let mut output = Vec::new();
+        Manual::hello_macro_with_config_to(&cfg, &mut output).unwrap();
+
+        assert_eq!(String::from_utf8(output).unwrap(), "Hi, Manual!\n");
+    }
+
+    #[test]
+    fn values_greet_through_their_type() {
+        let mut output = Vec::new();
+        Manual.greet_to(&mut output).unwrap();
+        Manual.greet();
+
+        assert_eq!(String::from_utf8(output).unwrap(), "Hello, Manual!\n");
+    }
+
+    #[test]
+    fn greet_to_forwards_to_the_type() {
+        let mut output = Vec::new();
+        greet_to::<Manual, _>(&mut output).unwrap();
+
+        assert_eq!(String::from_utf8(output).unwrap(), "Hello, Manual!\n");
+    }
 }